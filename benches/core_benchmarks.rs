@@ -0,0 +1,62 @@
+//! Benchmarks for the hot paths of the file manager core: directory
+//! refresh (including sorting) on large directories, glob selection, and
+//! the copy loop. Run with `cargo bench`.
+
+use std::fs;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tempfile::TempDir;
+
+use geekcommander::core::{copy_paths, execute_operation, PaneState};
+
+/// Populate a directory with `count` small files, half .txt and half .log
+fn populate(dir: &std::path::Path, count: usize) {
+    for i in 0..count {
+        let extension = if i % 2 == 0 { "txt" } else { "log" };
+        fs::write(dir.join(format!("file{:06}.{}", i, extension)), b"x").unwrap();
+    }
+}
+
+/// Refresh (read + sort) of a directory with 100k entries
+fn bench_refresh(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    populate(dir.path(), 100_000);
+    let mut pane = PaneState::new(dir.path().to_path_buf()).unwrap();
+
+    c.bench_function("refresh_100k_entries", |b| {
+        b.iter(|| pane.refresh().unwrap())
+    });
+}
+
+/// Glob selection across 10k entries
+fn bench_glob_selection(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    populate(dir.path(), 10_000);
+    let mut pane = PaneState::new(dir.path().to_path_buf()).unwrap();
+
+    c.bench_function("select_by_pattern_10k", |b| {
+        b.iter(|| {
+            let count = pane.select_by_pattern("*.log").unwrap();
+            pane.deselect_all();
+            count
+        })
+    });
+}
+
+/// The buffered copy loop on a single 8 MB file
+fn bench_copy(c: &mut Criterion) {
+    let src_dir = TempDir::new().unwrap();
+    let source = src_dir.path().join("payload.bin");
+    fs::write(&source, vec![0x5Au8; 8 * 1024 * 1024]).unwrap();
+
+    c.bench_function("copy_8mb_file", |b| {
+        b.iter(|| {
+            let dest_dir = TempDir::new().unwrap();
+            let mut operation = copy_paths(vec![source.clone()], dest_dir.path());
+            execute_operation(&mut operation).unwrap();
+        })
+    });
+}
+
+criterion_group!(benches, bench_refresh, bench_glob_selection, bench_copy);
+criterion_main!(benches);
@@ -1,19 +1,70 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 use std::io::{Read, Write};
-use std::fs::File;
-use zip::{ZipArchive, ZipWriter, CompressionMethod};
-use tar::Archive as TarArchive;
-use chrono::{DateTime, Local, TimeZone};
+use zip::write::FileOptions;
+use zip::ZipWriter;
 
 use crate::error::{GeekCommanderError, Result};
 use crate::core::ArchiveEntry;
+use crate::platform;
 
 /// Archive handler trait
 pub trait ArchiveHandler {
     fn list_entries(&self, path: &str) -> Result<Vec<ArchiveEntry>>;
     fn extract_file(&self, entry_path: &str, output: &mut dyn Write) -> Result<()>;
     fn extract_to_disk(&self, entry_path: &str, output_path: &Path) -> Result<()>;
+    /// Extract every entry into `dest_dir`, skipping malicious entries.
+    /// Returns the list of entry names that were skipped.
+    fn extract_all(&self, dest_dir: &Path) -> Result<Vec<String>>;
+}
+
+/// Returns true if the entry path stays inside the extraction directory
+/// (no absolute paths, drive prefixes, or `..` components)
+pub fn is_safe_entry_path(entry_path: &str) -> bool {
+    let path = Path::new(entry_path);
+    !path.is_absolute()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Refuse entry paths that could escape the extraction directory
+fn check_entry_path(entry_path: &str) -> Result<()> {
+    if !is_safe_entry_path(entry_path) {
+        return Err(GeekCommanderError::InvalidExtractionPath(format!(
+            "Entry '{}' would escape the extraction directory",
+            entry_path
+        )));
+    }
+    Ok(())
+}
+
+/// Recreate a symlink entry pointing at `target`
+fn create_symlink(target: &Path, link: &Path) -> Result<()> {
+    // Replace a stale entry at the destination, if any
+    if std::fs::symlink_metadata(link).is_ok() {
+        let _ = std::fs::remove_file(link);
+    }
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_file(target, link)?;
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (target, link);
+        Err(GeekCommanderError::Archive(
+            "Symlinks are not supported on this platform".to_string(),
+        ))
+    }
 }
 
 /// ZIP archive handler
@@ -60,23 +111,9 @@ impl ArchiveHandler for ZipHandler {
                     path: name.to_string(),
                     is_dir,
                     size: entry.size(),
-                    modified: {
-                        let dt = entry.last_modified();
-                        Local
-                            .timestamp_opt(946684800, 0) // Jan 1, 2000 as fallback
-                            .single()
-                            .unwrap_or_else(|| {
-                                Local.timestamp_opt(
-                                    946684800 + (dt.year() as i64 - 2000) * 365 * 24 * 3600 +
-                                    (dt.month() as i64 - 1) * 30 * 24 * 3600 +
-                                    (dt.day() as i64 - 1) * 24 * 3600 +
-                                    dt.hour() as i64 * 3600 +
-                                    dt.minute() as i64 * 60 +
-                                    dt.second() as i64,
-                                    0
-                                ).single().unwrap_or_else(|| Local.timestamp_opt(946684800, 0).single().unwrap())
-                            })
-                    },
+                    modified: zip_mtime_seconds(&entry.last_modified())
+                        .map(|secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs.max(0) as u64))
+                        .unwrap_or(SystemTime::UNIX_EPOCH),
                 };
                 entries.push(archive_entry);
             }
@@ -106,21 +143,81 @@ impl ArchiveHandler for ZipHandler {
     }
     
     fn extract_to_disk(&self, entry_path: &str, output_path: &Path) -> Result<()> {
+        check_entry_path(entry_path)?;
+
         let file = std::fs::File::open(&self.archive_path)?;
         let mut archive = zip::ZipArchive::new(file)?;
-        
+
         let mut entry = archive.by_name(entry_path)?;
-        
-        // Create parent directories if needed
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent)?;
+        unpack_zip_entry(&mut entry, output_path)
+    }
+
+    fn extract_all(&self, dest_dir: &Path) -> Result<Vec<String>> {
+        let file = std::fs::File::open(&self.archive_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        let mut skipped = Vec::new();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().to_string();
+
+            if !is_safe_entry_path(&name) {
+                log::warn!("Skipping malicious archive entry '{}'", name);
+                skipped.push(name);
+                continue;
+            }
+
+            let output_path = dest_dir.join(&name);
+            if entry.is_dir() {
+                std::fs::create_dir_all(&output_path)?;
+            } else {
+                unpack_zip_entry(&mut entry, &output_path)?;
+            }
         }
-        
-        let mut output_file = std::fs::File::create(output_path)?;
-        std::io::copy(&mut entry, &mut output_file)?;
-        
-        Ok(())
+
+        Ok(skipped)
+    }
+}
+
+/// Write a single ZIP entry to disk, recreating symlinks and applying the
+/// mode and mtime recorded in the archive header
+fn unpack_zip_entry(entry: &mut zip::read::ZipFile, output_path: &Path) -> Result<()> {
+    let mode = entry.unix_mode();
+    let mtime = zip_mtime_seconds(&entry.last_modified());
+
+    // Create parent directories if needed
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Symlink entries store their target as the file content
+    if mode.is_some_and(|m| m & 0o170000 == 0o120000) {
+        let mut target = String::new();
+        entry.read_to_string(&mut target)?;
+        return create_symlink(Path::new(&target), output_path);
     }
+
+    let mut output_file = std::fs::File::create(output_path)?;
+    std::io::copy(entry, &mut output_file)?;
+    drop(output_file);
+
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(output_path, std::fs::Permissions::from_mode(mode & 0o7777));
+    }
+    if let Some(seconds) = mtime {
+        let _ = platform::set_file_mtime(output_path, seconds);
+    }
+
+    Ok(())
+}
+
+/// Convert a ZIP header timestamp to Unix seconds, if it is representable
+fn zip_mtime_seconds(dt: &zip::DateTime) -> Option<i64> {
+    let date = chrono::NaiveDate::from_ymd_opt(dt.year() as i32, dt.month() as u32, dt.day() as u32)?;
+    let time = date.and_hms_opt(dt.hour() as u32, dt.minute() as u32, dt.second() as u32)?;
+    Some(time.and_utc().timestamp())
 }
 
 /// TAR archive handler
@@ -207,9 +304,15 @@ impl ArchiveHandler for TarHandler {
     }
     
     fn extract_to_disk(&self, entry_path: &str, output_path: &Path) -> Result<()> {
+        check_entry_path(entry_path)?;
+
         let file = std::fs::File::open(&self.archive_path)?;
         let mut archive = tar::Archive::new(file);
-        
+        // unpack() recreates symlinks itself and applies the header's
+        // mode and mtime when these are enabled
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+
         for entry_result in archive.entries()? {
             let mut entry = entry_result?;
             let path = entry.path()?;
@@ -218,15 +321,41 @@ impl ArchiveHandler for TarHandler {
                 if let Some(parent) = output_path.parent() {
                     std::fs::create_dir_all(parent)?;
                 }
-                
-                let mut output_file = std::fs::File::create(output_path)?;
-                std::io::copy(&mut entry, &mut output_file)?;
+
+                entry.unpack(output_path)?;
                 return Ok(());
             }
         }
-        
+
         Err(GeekCommanderError::archive(format!("Entry '{}' not found in archive", entry_path)))
     }
+
+    fn extract_all(&self, dest_dir: &Path) -> Result<Vec<String>> {
+        let file = std::fs::File::open(&self.archive_path)?;
+        let mut archive = tar::Archive::new(file);
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+
+        let mut skipped = Vec::new();
+        for entry_result in archive.entries()? {
+            let mut entry = entry_result?;
+            let name = entry.path()?.to_string_lossy().to_string();
+
+            if !is_safe_entry_path(&name) {
+                log::warn!("Skipping malicious archive entry '{}'", name);
+                skipped.push(name);
+                continue;
+            }
+
+            let output_path = dest_dir.join(&name);
+            if let Some(parent) = output_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            entry.unpack(&output_path)?;
+        }
+
+        Ok(skipped)
+    }
 }
 
 /// Create an appropriate archive handler for the given file
@@ -276,7 +405,6 @@ pub fn is_supported_archive(path: &Path) -> bool {
 pub fn add_to_zip_archive(archive_path: &Path, files: &[&Path]) -> Result<()> {
     let file = if archive_path.exists() {
         std::fs::OpenOptions::new()
-            .write(true)
             .append(true)
             .open(archive_path)?
     } else {
@@ -328,4 +456,242 @@ pub fn add_to_zip_archive(archive_path: &Path, files: &[&Path]) -> Result<()> {
     
     zip.finish()?;
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Per-entry metadata shown in the info dialog for ZIP archives
+#[derive(Debug, Clone)]
+pub struct ZipEntryInfo {
+    pub name: String,
+    /// Compression method as recorded in the entry header
+    pub method: String,
+    pub size: u64,
+    pub compressed_size: u64,
+    pub crc32: u32,
+}
+
+/// The archive-level comment of a ZIP file (empty when none is set)
+pub fn zip_comment(archive_path: &Path) -> Result<String> {
+    let file = std::fs::File::open(archive_path)?;
+    let archive = zip::ZipArchive::new(file)?;
+    Ok(String::from_utf8_lossy(archive.comment()).to_string())
+}
+
+/// Collect compression method, sizes and CRC for every file entry
+pub fn zip_entry_infos(archive_path: &Path) -> Result<Vec<ZipEntryInfo>> {
+    let file = std::fs::File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let mut infos = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        infos.push(ZipEntryInfo {
+            name: entry.name().to_string(),
+            method: format!("{:?}", entry.compression()),
+            size: entry.size(),
+            compressed_size: entry.compressed_size(),
+            crc32: entry.crc32(),
+        });
+    }
+    Ok(infos)
+}
+
+/// Replace the archive-level comment by rewriting the ZIP. Entries are
+/// raw-copied (no recompression); the rewrite lands in a sibling temp file
+/// that replaces the original only once it is complete.
+pub fn set_zip_comment(archive_path: &Path, comment: &str) -> Result<()> {
+    let file = std::fs::File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let tmp_path = archive_path.with_extension("zip.tmp");
+    let mut writer = ZipWriter::new(std::fs::File::create(&tmp_path)?);
+    writer.set_comment(comment);
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        writer.raw_copy_file(entry)?;
+    }
+    writer.finish()?;
+    drop(archive);
+
+    std::fs::rename(&tmp_path, archive_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_entry_path_rejects_traversal() {
+        assert!(check_entry_path("file.txt").is_ok());
+        assert!(check_entry_path("dir/file.txt").is_ok());
+        assert!(check_entry_path("../evil.txt").is_err());
+        assert!(check_entry_path("dir/../../evil.txt").is_err());
+        assert!(check_entry_path("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_extract_all_skips_malicious_entries() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("slip.zip");
+
+        let file = std::fs::File::create(&archive_path)?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default();
+        zip.start_file("good.txt", options)?;
+        zip.write_all(b"safe")?;
+        zip.start_file("../evil.txt", options)?;
+        zip.write_all(b"escape")?;
+        zip.start_file("nested/also-good.txt", options)?;
+        zip.write_all(b"safe too")?;
+        zip.finish()?;
+
+        let dest = temp_dir.path().join("out");
+        let handler = ZipHandler::new(archive_path);
+        let skipped = handler.extract_all(&dest)?;
+
+        assert_eq!(skipped, vec!["../evil.txt".to_string()]);
+        assert_eq!(std::fs::read_to_string(dest.join("good.txt"))?, "safe");
+        assert_eq!(std::fs::read_to_string(dest.join("nested/also-good.txt"))?, "safe too");
+        assert!(!temp_dir.path().join("evil.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tar_extract_all_skips_malicious_entries() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("slip.tar");
+
+        let mut builder = tar::Builder::new(std::fs::File::create(&archive_path)?);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "good.txt", &b"safe"[..])
+            .map_err(|e| GeekCommanderError::archive(format!("Failed to build test tar: {}", e)))?;
+        // tar::Builder refuses `..` itself, so write the name into the raw header
+        let mut header = tar::Header::new_gnu();
+        let evil_name = b"up/../../evil.txt";
+        header.as_gnu_mut().unwrap().name[..evil_name.len()].copy_from_slice(evil_name);
+        header.set_size(6);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &b"escape"[..])?;
+        builder.finish()?;
+
+        let dest = temp_dir.path().join("out");
+        let handler = TarHandler::new(archive_path);
+        let skipped = handler.extract_all(&dest)?;
+
+        assert_eq!(skipped, vec!["up/../../evil.txt".to_string()]);
+        assert_eq!(std::fs::read_to_string(dest.join("good.txt"))?, "safe");
+        assert!(!temp_dir.path().join("evil.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zip_extract_preserves_mode_and_mtime() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.zip");
+
+        // Even seconds only: DOS timestamps have two-second resolution
+        let stamp = zip::DateTime::from_date_and_time(2020, 1, 2, 3, 4, 4)
+            .expect("valid timestamp");
+        let file = std::fs::File::create(&archive_path)?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default()
+            .unix_permissions(0o755)
+            .last_modified_time(stamp);
+        zip.start_file("script.sh", options)?;
+        zip.write_all(b"#!/bin/sh\n")?;
+        zip.finish()?;
+
+        let handler = ZipHandler::new(archive_path);
+        let output = temp_dir.path().join("script.sh");
+        handler.extract_to_disk("script.sh", &output)?;
+
+        assert_eq!(std::fs::read_to_string(&output)?, "#!/bin/sh\n");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&output)?.permissions().mode() & 0o777, 0o755);
+
+            let expected = SystemTime::UNIX_EPOCH
+                + std::time::Duration::from_secs(zip_mtime_seconds(&stamp).unwrap() as u64);
+            assert_eq!(std::fs::metadata(&output)?.modified()?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tar_extract_recreates_symlinks() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.tar");
+
+        let mut builder = tar::Builder::new(std::fs::File::create(&archive_path)?);
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "link.txt", "target.txt")
+            .map_err(|e| GeekCommanderError::archive(format!("Failed to build test tar: {}", e)))?;
+        builder.finish()?;
+
+        let handler = TarHandler::new(archive_path);
+        let output = temp_dir.path().join("link.txt");
+        handler.extract_to_disk("link.txt", &output)?;
+
+        assert!(std::fs::symlink_metadata(&output)?.file_type().is_symlink());
+        assert_eq!(std::fs::read_link(&output)?, PathBuf::from("target.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_refuses_escaping_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let handler = ZipHandler::new(temp_dir.path().join("missing.zip"));
+
+        let result = handler.extract_to_disk("../evil.txt", &temp_dir.path().join("out"));
+        assert!(matches!(result, Err(GeekCommanderError::InvalidExtractionPath(_))));
+    }
+
+    #[test]
+    fn test_zip_comment_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("commented.zip");
+
+        let file = std::fs::File::create(&archive_path)?;
+        let mut zip = ZipWriter::new(file);
+        zip.start_file("readme.txt", FileOptions::default())?;
+        zip.write_all(b"hello archive")?;
+        zip.finish()?;
+
+        assert_eq!(zip_comment(&archive_path)?, "");
+
+        set_zip_comment(&archive_path, "nightly build 42")?;
+        assert_eq!(zip_comment(&archive_path)?, "nightly build 42");
+
+        // The rewrite must preserve the entries themselves.
+        let infos = zip_entry_infos(&archive_path)?;
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos[0].name, "readme.txt");
+        assert_eq!(infos[0].size, 13);
+        assert_ne!(infos[0].crc32, 0);
+
+        let handler = ZipHandler::new(archive_path);
+        let dest = temp_dir.path().join("out");
+        handler.extract_all(&dest)?;
+        assert_eq!(std::fs::read_to_string(dest.join("readme.txt"))?, "hello archive");
+
+        Ok(())
+    }
+}
\ No newline at end of file
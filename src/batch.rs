@@ -0,0 +1,249 @@
+//! Scriptable batch mode: executes a simple command script without drawing
+//! the TUI, reusing the same core operations as the interactive app. Useful
+//! for automation and for reproducing bug reports deterministically.
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::core::{
+    copy_files, create_directories, delete_files, execute_operation, move_files, PaneState,
+};
+use crate::error::{GeekCommanderError, Result};
+
+/// Entry point for `--batch`: reads the script from stdin and reports each
+/// command's outcome on stdout.
+pub fn run(config: &Config) -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    run_script(config, stdin.lock(), &mut stdout)
+}
+
+/// Execute a command script, one command per line. Supported commands:
+///
+/// ```text
+/// cd <path>        change the active pane's directory
+/// switch           make the other pane active
+/// select <glob>    select matching entries in the active pane
+/// copy             copy the selection to the other pane's directory
+/// move             move the selection to the other pane's directory
+/// delete           delete the selection
+/// mkdir <name>     create directories (a/b/c nests, ; separates several)
+/// quit             stop processing (implicit at end of input)
+/// ```
+///
+/// Blank lines and lines starting with `#` are skipped. The first failing
+/// command aborts the script with an error naming its line number.
+pub fn run_script<R: BufRead, W: Write>(config: &Config, input: R, output: &mut W) -> Result<()> {
+    let mut left = PaneState::new(config.panels.left.clone())?;
+    let mut right = PaneState::new(config.panels.right.clone())?;
+    left.case_sensitivity = config.general.case_sensitivity;
+    right.case_sensitivity = config.general.case_sensitivity;
+    let mut active = 0usize;
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.map_err(GeekCommanderError::Io)?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+
+        let result = execute_command(
+            config,
+            &mut left,
+            &mut right,
+            &mut active,
+            command,
+            argument,
+            output,
+        );
+        match result {
+            Ok(true) => {}
+            Ok(false) => break, // quit
+            Err(e) => {
+                return Err(GeekCommanderError::FileOperation(format!(
+                    "Batch line {}: {}",
+                    idx + 1,
+                    e
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one script command; `Ok(false)` means `quit` was seen.
+#[allow(clippy::too_many_arguments)]
+fn execute_command<W: Write>(
+    config: &Config,
+    left: &mut PaneState,
+    right: &mut PaneState,
+    active: &mut usize,
+    command: &str,
+    argument: &str,
+    output: &mut W,
+) -> Result<bool> {
+    let (pane, other) = if *active == 0 { (left, right) } else { (right, left) };
+
+    match command {
+        "cd" => {
+            if argument.is_empty() {
+                return Err(GeekCommanderError::FileOperation("cd needs a path".to_string()));
+            }
+            let target = resolve_against(&pane.current_path, argument);
+            if !target.is_dir() {
+                return Err(GeekCommanderError::FileOperation(format!(
+                    "'{}' is not a directory",
+                    target.display()
+                )));
+            }
+            pane.enter_directory(target)?;
+            writeln!(output, "cd {}", pane.current_path.display())?;
+        }
+        "switch" => {
+            *active = if *active == 0 { 1 } else { 0 };
+            writeln!(output, "active pane: {}", if *active == 0 { "left" } else { "right" })?;
+        }
+        "select" => {
+            let count = pane.select_by_pattern(argument)?;
+            writeln!(output, "selected {} entries", count)?;
+        }
+        "copy" | "move" => {
+            let sources = pane.get_selected_entries();
+            if sources.is_empty() {
+                return Err(GeekCommanderError::FileOperation("nothing selected".to_string()));
+            }
+            let destination = other.current_path.clone();
+            let mut operation = if command == "copy" {
+                copy_files(&sources, &destination)?
+            } else {
+                move_files(&sources, &destination)?
+            };
+            execute_operation(&mut operation)?;
+            writeln!(output, "{} {} file(s) to {}", command, operation.files_completed, destination.display())?;
+            pane.deselect_all();
+            pane.refresh()?;
+            other.refresh()?;
+        }
+        "delete" => {
+            let sources = pane.get_selected_entries();
+            if sources.is_empty() {
+                return Err(GeekCommanderError::FileOperation("nothing selected".to_string()));
+            }
+            let mut operation = delete_files(&sources)?;
+            execute_operation(&mut operation)?;
+            writeln!(output, "deleted {} file(s)", operation.files_completed)?;
+            pane.deselect_all();
+            pane.refresh()?;
+        }
+        "mkdir" => {
+            if argument.is_empty() {
+                return Err(GeekCommanderError::FileOperation("mkdir needs a name".to_string()));
+            }
+            let created = create_directories(&pane.current_path, argument, config.general.new_dir_mode)?;
+            writeln!(output, "created {} directorie(s)", created.len())?;
+            pane.refresh()?;
+        }
+        "quit" => {
+            writeln!(output, "quit")?;
+            return Ok(false);
+        }
+        other_command => {
+            return Err(GeekCommanderError::FileOperation(format!(
+                "unknown command '{}'",
+                other_command
+            )))
+        }
+    }
+
+    Ok(true)
+}
+
+/// Resolve a script path argument against the pane's current directory
+fn resolve_against(base: &std::path::Path, argument: &str) -> PathBuf {
+    let path = PathBuf::from(argument);
+    if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn batch_config(left: &std::path::Path, right: &std::path::Path) -> Config {
+        let mut config = Config::default();
+        config.panels.left = left.to_path_buf();
+        config.panels.right = right.to_path_buf();
+        config
+    }
+
+    #[test]
+    fn test_batch_copy_script() -> Result<()> {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+        std::fs::write(left.path().join("a.txt"), "hello")?;
+        std::fs::write(left.path().join("b.log"), "noise")?;
+
+        let script = "# copy only the .txt file across\nselect *.txt\ncopy\nquit\n";
+        let mut output = Vec::new();
+        run_script(
+            &batch_config(left.path(), right.path()),
+            script.as_bytes(),
+            &mut output,
+        )?;
+
+        assert!(right.path().join("a.txt").exists());
+        assert!(!right.path().join("b.log").exists());
+        let transcript = String::from_utf8(output).unwrap();
+        assert!(transcript.contains("selected 1 entries"));
+        assert!(transcript.contains("copy 1 file(s)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_unknown_command_reports_line() {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+
+        let script = "switch\npack everything\n";
+        let mut output = Vec::new();
+        let err = run_script(
+            &batch_config(left.path(), right.path()),
+            script.as_bytes(),
+            &mut output,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_batch_cd_and_mkdir() -> Result<()> {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+
+        let script = "mkdir a/b; docs\ncd a\nmkdir c\n";
+        let mut output = Vec::new();
+        run_script(
+            &batch_config(left.path(), right.path()),
+            script.as_bytes(),
+            &mut output,
+        )?;
+
+        assert!(left.path().join("a").join("b").is_dir());
+        assert!(left.path().join("docs").is_dir());
+        assert!(left.path().join("a").join("c").is_dir());
+
+        Ok(())
+    }
+}
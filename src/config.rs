@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::fs;
 use crossterm::event::{KeyCode, KeyModifiers};
 use tui::style::Color;
+use crate::core::{CaseSensitivity, DirsPlacement};
 use crate::error::{GeekCommanderError, Result};
 
 #[derive(Debug, Clone)]
@@ -11,7 +12,23 @@ pub struct Config {
     pub colors: ColorScheme,
     pub panels: PanelConfig,
     pub general: GeneralConfig,
+    pub confirmation: ConfirmationConfig,
     pub logging: LoggingConfig,
+    /// What Enter does per file extension (lowercased, without the dot),
+    /// from the `[EnterActions]` section; unlisted extensions use the viewer
+    pub enter_actions: HashMap<String, EnterAction>,
+    /// Viewer size cap per file extension (lowercased, without the dot),
+    /// in bytes, from the `[ViewerLimits]` section. The special key
+    /// `default` replaces the built-in 50MB cap for unlisted extensions
+    pub viewer_limits: HashMap<String, u64>,
+    /// Portable mode keeps config, logs and state beside the executable
+    pub portable: bool,
+    /// Overrides where session/state files live; set by tests so they never
+    /// touch the real state directory
+    pub state_dir_override: Option<PathBuf>,
+    /// Resolved location of the config file, so changed settings (like the
+    /// pane split) can be written back to wherever they were loaded from
+    config_file: PathBuf,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +47,14 @@ pub struct Keybindings {
     pub wildcard: KeyBinding,
     pub reload: KeyBinding,
     pub switch_pane: KeyBinding,
+    pub clipboard_copy: KeyBinding,
+    pub clipboard_cut: KeyBinding,
+    pub clipboard_paste: KeyBinding,
+    pub info: KeyBinding,
+    pub hotpath: KeyBinding,
+    pub compare: KeyBinding,
+    /// Swap the active pane between its current and previous directory
+    pub prev_dir: KeyBinding,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +78,8 @@ pub struct ColorScheme {
 pub struct PanelConfig {
     pub left: PathBuf,
     pub right: PathBuf,
+    /// Width of the left pane as a percentage (10-90)
+    pub split: u16,
 }
 
 #[derive(Debug, Clone)]
@@ -61,13 +88,104 @@ pub struct GeneralConfig {
     pub confirm_delete: bool,
     pub confirm_overwrite: bool,
     pub use_colors: bool,
+    /// Dereference symlinks when copying (copy the target's contents).
+    /// Off by default: links are recreated as links at the destination.
     pub follow_symlinks: bool,
+    /// Explicit mode for new directories (e.g. `NewDirMode=755`);
+    /// `None` leaves the result to the process umask
+    pub new_dir_mode: Option<u32>,
+    /// `DirsFirst=true` (default), `false` for alphabetical mixing,
+    /// or `last` to sort directories after files
+    pub dirs_placement: DirsPlacement,
+    /// Show the hard link count next to the size for multiply-linked files
+    pub show_link_count: bool,
+    /// `CaseSensitivity=sensitive` (default), `insensitive`, or `smart`
+    /// (insensitive unless the pattern contains uppercase)
+    pub case_sensitivity: CaseSensitivity,
+    /// `NavigationStyle=classic` (default) or `arrows` to give the
+    /// Left/Right keys pane-switching semantics
+    pub navigation_style: NavigationStyle,
+    /// Compute recursive directory sizes in the background and show them
+    /// in the Size column. Off by default: walking large trees is expensive
+    pub show_dir_sizes: bool,
+    /// Count each directory's immediate children in the background and show
+    /// the number next to `<DIR>`. Off by default
+    pub show_dir_counts: bool,
+    /// Show a permissions column in the panes, with the rwx triads colored
+    /// per user/group/other; Alt+X switches it to octal. Off by default
+    pub show_permissions: bool,
+    /// Re-read pane directories every this many seconds when their mtime
+    /// changed, for filesystems where change notification does not work
+    /// (NFS, SMB, SSHFS). 0 (default) disables the periodic refresh
+    pub auto_refresh_secs: u64,
+    /// Render the selection highlight in reverse video with bold text so
+    /// selected rows stand out regardless of the configured colors
+    pub high_contrast_selection: bool,
+}
+
+/// What the unmodified Left/Right arrow keys do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NavigationStyle {
+    /// Left/Right do nothing (Norton Commander behavior)
+    #[default]
+    Classic,
+    /// Left focuses the left pane (or goes to the parent when already there);
+    /// Right focuses the right pane (or enters the directory under the cursor)
+    Arrows,
+}
+
+/// Which operations ask for confirmation before running ([Confirmation]).
+/// Each toggle is independent of the others.
+#[derive(Debug, Clone)]
+pub struct ConfirmationConfig {
+    /// Ask before deleting; overrides the legacy [General] ConfirmDelete
+    /// when set
+    pub delete: Option<bool>,
+    /// Ask before overwriting existing destination entries; overrides the
+    /// legacy [General] ConfirmOverwrite when set
+    pub overwrite: Option<bool>,
+    /// Ask before moving
+    pub moves: bool,
+    /// Ask before operations involving more than this many files (0 = never)
+    pub bulk_files: u64,
+    /// Ask before operations touching more than this many megabytes (0 = never)
+    pub bulk_size_mb: u64,
+    /// Ask before quitting while a background operation is still running
+    pub exit_with_jobs: bool,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        ConfirmationConfig {
+            delete: None,
+            overwrite: None,
+            moves: true,
+            bulk_files: 0,
+            bulk_size_mb: 0,
+            exit_with_jobs: true,
+        }
+    }
+}
+
+/// What pressing Enter on a file does. Keys in `[EnterActions]` are
+/// extension groups (e.g. `jpg,png,gif=open`), values one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnterAction {
+    /// Open in the built-in viewer (the default)
+    View,
+    /// Open in the configured external editor
+    Edit,
+    /// Hand off to the application the OS associates with the file
+    Open,
 }
 
 #[derive(Debug, Clone)]
 pub struct LoggingConfig {
     pub level: String,
     pub file: PathBuf,
+    /// JSONL file recording every file touched by copy/move/delete
+    /// operations; auditing is off when unset
+    pub audit_file: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -77,7 +195,13 @@ impl Default for Config {
             colors: ColorScheme::default(),
             panels: PanelConfig::default(),
             general: GeneralConfig::default(),
+            confirmation: ConfirmationConfig::default(),
             logging: LoggingConfig::default(),
+            enter_actions: HashMap::new(),
+            viewer_limits: HashMap::new(),
+            portable: false,
+            state_dir_override: None,
+            config_file: Self::get_default_config_path(false),
         }
     }
 }
@@ -99,6 +223,13 @@ impl Default for Keybindings {
             wildcard: KeyBinding::new(KeyCode::Char('*'), KeyModifiers::NONE),
             reload: KeyBinding::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
             switch_pane: KeyBinding::new(KeyCode::Tab, KeyModifiers::NONE),
+            clipboard_copy: KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            clipboard_cut: KeyBinding::new(KeyCode::Char('x'), KeyModifiers::CONTROL),
+            clipboard_paste: KeyBinding::new(KeyCode::Char('v'), KeyModifiers::CONTROL),
+            info: KeyBinding::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+            hotpath: KeyBinding::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
+            compare: KeyBinding::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            prev_dir: KeyBinding::new(KeyCode::Char('-'), KeyModifiers::ALT),
         }
     }
 }
@@ -107,12 +238,12 @@ impl Default for ColorScheme {
     fn default() -> Self {
         ColorScheme {
             active_pane_border: Color::Cyan,
-            inactive_pane_border: Color::Blue,
+            inactive_pane_border: Color::DarkGray,
             selected_item: Color::Black,
             status_bar: Color::Cyan,
             directory_fg: Color::White,
             file_fg: Color::Cyan,
-            cursor_bg: Color::Blue,
+            cursor_bg: Color::Cyan,
         }
     }
 }
@@ -123,6 +254,7 @@ impl Default for PanelConfig {
         PanelConfig {
             left: home.clone(),
             right: home,
+            split: 50,
         }
     }
 }
@@ -134,20 +266,27 @@ impl Default for GeneralConfig {
             confirm_delete: true,
             confirm_overwrite: true,
             use_colors: true,
-            follow_symlinks: true,
+            follow_symlinks: false,
+            new_dir_mode: None,
+            dirs_placement: DirsPlacement::First,
+            show_link_count: false,
+            case_sensitivity: CaseSensitivity::Sensitive,
+            navigation_style: NavigationStyle::Classic,
+            show_dir_sizes: false,
+            show_dir_counts: false,
+            show_permissions: false,
+            auto_refresh_secs: 0,
+            high_contrast_selection: false,
         }
     }
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
-        let log_file = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".geekcommander.log");
-        
         LoggingConfig {
             level: "INFO".to_string(),
-            file: log_file,
+            file: Config::state_dir(false).join("geekcommander.log"),
+            audit_file: None,
         }
     }
 }
@@ -164,26 +303,258 @@ impl KeyBinding {
 
 impl Config {
     pub fn load_or_create_default(config_path: Option<&str>) -> Result<Self> {
+        Self::load_with_mode(config_path, false)
+    }
+
+    pub fn load_with_mode(config_path: Option<&str>, portable: bool) -> Result<Self> {
         let config_file = match config_path {
             Some(path) => PathBuf::from(path),
-            None => Self::get_default_config_path(),
+            None => Self::get_default_config_path(portable),
         };
 
-        if config_file.exists() {
-            Self::load_from_file(&config_file)
+        let mut config = if config_file.exists() {
+            Self::load_from_file(&config_file)?
         } else {
             let config = Config::default();
-            if let Err(e) = config.save_to_file(&config_file) {
-                log::warn!("Failed to create default config file: {}", e);
+            config.save_defaults_in_background(&config_file);
+            config
+        };
+
+        config.portable = portable;
+        config.config_file = config_file;
+        if portable {
+            config.logging.file = Self::state_dir(true).join("geekcommander.log");
+        }
+
+        // Environment variables win over anything from the file
+        config.apply_env_overrides();
+
+        Ok(config)
+    }
+
+    /// Write the current settings back to the file they were loaded from
+    pub fn save(&self) -> Result<()> {
+        self.save_to_file(&self.config_file)
+    }
+
+    /// Apply `GEEKCMD_<SECTION>_<KEY>` environment variable overrides
+    /// (e.g. `GEEKCMD_GENERAL_SHOWHIDDEN=1`, `GEEKCMD_PANELS_LEFT=/srv`),
+    /// resolved after file parsing so containers and one-off sessions can
+    /// tweak individual values without editing the config.
+    fn apply_env_overrides(&mut self) {
+        for (var, value) in std::env::vars() {
+            let rest = match var.strip_prefix("GEEKCMD_") {
+                Some(rest) => rest,
+                None => continue,
+            };
+
+            let (section, key) = match rest.split_once('_') {
+                Some(parts) => parts,
+                None => {
+                    log::warn!("Ignoring malformed override {} (expected GEEKCMD_SECTION_KEY)", var);
+                    continue;
+                }
+            };
+
+            if let Err(e) = self.apply_override(section, key, &value) {
+                log::warn!("Ignoring invalid override {}={}: {}", var, value, e);
+            }
+        }
+    }
+
+    /// Apply a single override; `section` and `key` are matched case-insensitively
+    fn apply_override(&mut self, section: &str, key: &str, value: &str) -> Result<()> {
+        // [EnterActions] keys are free-form extension groups, not a fixed list
+        if section.eq_ignore_ascii_case("EnterActions") {
+            let mut entry = HashMap::new();
+            entry.insert(key.to_string(), value.to_string());
+            return parse_enter_actions(&entry, &mut self.enter_actions);
+        }
+
+        if section.eq_ignore_ascii_case("ViewerLimits") {
+            let mut entry = HashMap::new();
+            entry.insert(key.to_string(), value.to_string());
+            return parse_viewer_limits(&entry, &mut self.viewer_limits);
+        }
+
+        const SECTION_KEYS: &[(&str, &[&str])] = &[
+            ("Keybindings", &[
+                "Help", "Copy", "Move", "Delete", "Rename", "NewDir", "Quit", "View", "Edit",
+                "Select", "SelectAll", "Wildcard", "Reload", "SwitchPane",
+                "ClipboardCopy", "ClipboardCut", "ClipboardPaste", "Info", "HotPath", "Compare",
+                "PrevDir",
+            ]),
+            ("Colors", &[
+                "ActivePaneBorder", "InactivePaneBorder", "SelectedItem", "StatusBar",
+                "DirectoryFg", "FileFg", "CursorBg",
+            ]),
+            ("Panels", &["Left", "Right", "Split"]),
+            ("General", &[
+                "ShowHidden", "ConfirmDelete", "ConfirmOverwrite", "UseColors", "FollowSymlinks",
+                "NewDirMode", "DirsFirst", "ShowLinkCount", "CaseSensitivity",
+                "NavigationStyle", "ShowDirSizes", "ShowDirCounts", "HighContrastSelection",
+                "AutoRefreshSeconds", "ShowPermissions",
+            ]),
+            ("Confirmation", &["Delete", "Overwrite", "Move", "BulkFiles", "BulkSizeMB", "ExitWithJobs"]),
+            ("Logging", &["Level", "File", "AuditFile"]),
+        ];
+
+        let (canonical_section, keys) = SECTION_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(section))
+            .ok_or_else(|| GeekCommanderError::Config(format!("Unknown section: {}", section)))?;
+
+        let canonical_key = keys
+            .iter()
+            .find(|k| k.eq_ignore_ascii_case(key))
+            .ok_or_else(|| GeekCommanderError::Config(format!("Unknown key: {}", key)))?;
+
+        let mut entry = HashMap::new();
+        entry.insert(canonical_key.to_string(), value.to_string());
+
+        match *canonical_section {
+            "Keybindings" => parse_keybindings(&entry, &mut self.keybindings),
+            "Colors" => parse_colors(&entry, &mut self.colors),
+            "Panels" => parse_panels(&entry, &mut self.panels),
+            "General" => parse_general(&entry, &mut self.general),
+            "Confirmation" => parse_confirmation(&entry, &mut self.confirmation),
+            "Logging" => parse_logging(&entry, &mut self.logging),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Load the config while collecting every problem (bad color, unknown key,
+    /// bad binding, ...) into a report with line references, instead of failing
+    /// on the first error. Invalid values keep their defaults.
+    pub fn load_with_report(config_path: Option<&str>, portable: bool) -> (Self, Vec<String>) {
+        let config_file = match config_path {
+            Some(path) => PathBuf::from(path),
+            None => Self::get_default_config_path(portable),
+        };
+
+        let mut config = Config::default();
+        let mut problems = Vec::new();
+
+        if config_file.exists() {
+            match fs::read_to_string(&config_file) {
+                Ok(content) => problems = config.apply_content_validated(&content),
+                Err(e) => problems.push(format!("Failed to read {}: {}", config_file.display(), e)),
             }
-            Ok(config)
+        } else {
+            config.save_defaults_in_background(&config_file);
         }
+
+        config.portable = portable;
+        config.config_file = config_file;
+        if portable {
+            config.logging.file = Self::state_dir(true).join("geekcommander.log");
+        }
+
+        config.apply_env_overrides();
+
+        (config, problems)
     }
 
-    fn get_default_config_path() -> PathBuf {
-        dirs::home_dir()
+    /// Apply INI content line by line, returning a report of every invalid
+    /// line. Good values are applied even when other lines are broken.
+    fn apply_content_validated(&mut self, content: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut current_section = String::new();
+
+        for (idx, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                current_section = line[1..line.len() - 1].to_string();
+                continue;
+            }
+
+            let line_no = idx + 1;
+            match line.find('=') {
+                Some(eq_pos) => {
+                    let key = line[..eq_pos].trim();
+                    let value = line[eq_pos + 1..].trim();
+                    if current_section.is_empty() {
+                        problems.push(format!("line {}: '{}' appears before any [Section]", line_no, key));
+                    } else if let Err(e) = self.apply_override(&current_section, key, value) {
+                        problems.push(format!("line {}: {}", line_no, e));
+                    }
+                },
+                None => {
+                    problems.push(format!("line {}: not a key=value pair: '{}'", line_no, line));
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Resolve the config file location.
+    ///
+    /// Portable mode keeps the config beside the executable. Otherwise the
+    /// legacy `~/.geekcommanderrc` is honored if it exists, and new installs
+    /// get the XDG location (`$XDG_CONFIG_HOME/geekcommander/config`, or the
+    /// platform equivalent such as `%APPDATA%` on Windows).
+    fn get_default_config_path(portable: bool) -> PathBuf {
+        if portable {
+            return Self::exe_dir().join("geekcommander.ini");
+        }
+
+        let legacy = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".geekcommanderrc");
+        if legacy.exists() {
+            return legacy;
+        }
+
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("geekcommander")
+            .join("config")
+    }
+
+    /// Directory for this configuration's session/state files: the test
+    /// override when set, otherwise the shared state directory
+    pub fn resolved_state_dir(&self) -> PathBuf {
+        match &self.state_dir_override {
+            Some(dir) => dir.clone(),
+            None => Self::state_dir(self.portable),
+        }
+    }
+
+    /// Directory for logs and session/state files
+    /// (`$XDG_STATE_HOME/geekcommander` or the platform equivalent)
+    pub fn state_dir(portable: bool) -> PathBuf {
+        if portable {
+            return Self::exe_dir();
+        }
+
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("geekcommander")
+    }
+
+    /// Whether deletes need confirming, preferring [Confirmation] Delete
+    /// over the legacy [General] ConfirmDelete
+    pub fn confirm_delete(&self) -> bool {
+        self.confirmation.delete.unwrap_or(self.general.confirm_delete)
+    }
+
+    /// Whether overwrites need confirming, preferring [Confirmation]
+    /// Overwrite over the legacy [General] ConfirmOverwrite
+    pub fn confirm_overwrite(&self) -> bool {
+        self.confirmation.overwrite.unwrap_or(self.general.confirm_overwrite)
+    }
+
+    fn exe_dir() -> PathBuf {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
             .unwrap_or_else(|| PathBuf::from("."))
-            .join(".geekcommanderrc")
     }
 
     fn load_from_file(path: &Path) -> Result<Self> {
@@ -195,32 +566,60 @@ impl Config {
 
         // Parse keybindings
         if let Some(keybindings) = sections.get("Keybindings") {
-            config.keybindings = parse_keybindings(keybindings)?;
+            parse_keybindings(keybindings, &mut config.keybindings)?;
         }
 
         // Parse colors
         if let Some(colors) = sections.get("Colors") {
-            config.colors = parse_colors(colors)?;
+            parse_colors(colors, &mut config.colors)?;
         }
 
         // Parse panels
         if let Some(panels) = sections.get("Panels") {
-            config.panels = parse_panels(panels)?;
+            parse_panels(panels, &mut config.panels)?;
         }
 
         // Parse general settings
         if let Some(general) = sections.get("General") {
-            config.general = parse_general(general)?;
+            parse_general(general, &mut config.general)?;
+        }
+
+        // Parse confirmation toggles
+        if let Some(confirmation) = sections.get("Confirmation") {
+            parse_confirmation(confirmation, &mut config.confirmation)?;
         }
 
         // Parse logging
         if let Some(logging) = sections.get("Logging") {
-            config.logging = parse_logging(logging)?;
+            parse_logging(logging, &mut config.logging)?;
+        }
+
+        // Parse per-extension Enter actions
+        if let Some(enter_actions) = sections.get("EnterActions") {
+            parse_enter_actions(enter_actions, &mut config.enter_actions)?;
+        }
+
+        // Parse per-extension viewer size caps
+        if let Some(viewer_limits) = sections.get("ViewerLimits") {
+            parse_viewer_limits(viewer_limits, &mut config.viewer_limits)?;
         }
 
         Ok(config)
     }
 
+    /// Write the default config from a background thread. Nothing reads the
+    /// file back during this session, and writing it synchronously would put
+    /// a network round-trip on the startup path for NFS home directories.
+    fn save_defaults_in_background(&self, path: &Path) {
+        let defaults = self.clone();
+        let path = path.to_path_buf();
+        std::thread::spawn(move || {
+            if let Err(e) = defaults.save_to_file(&path) {
+                log::warn!("Failed to create default config file: {}", e);
+            }
+        });
+    }
+
     fn save_to_file(&self, path: &Path) -> Result<()> {
         let content = self.to_ini_string();
         
@@ -229,7 +628,8 @@ impl Config {
                 .map_err(|e| GeekCommanderError::Config(format!("Failed to create config directory: {}", e)))?;
         }
 
-        fs::write(path, content)
+        // Write via temp file + rename so a crash mid-write can't corrupt the config
+        crate::platform::atomic_write(path, &content)
             .map_err(|e| GeekCommanderError::Config(format!("Failed to write config file: {}", e)))?;
 
         Ok(())
@@ -252,6 +652,12 @@ impl Config {
             Wildcard=*\n\
             Reload=Ctrl+R\n\
             SwitchPane=Tab\n\
+            ClipboardCopy=Ctrl+C\n\
+            ClipboardCut=Ctrl+X\n\
+            ClipboardPaste=Ctrl+V\n\
+            Info=Ctrl+L\n\
+            HotPath=Ctrl+J\n\
+            Compare=Ctrl+D\n\
             \n\
             [Colors]\n\
             ActivePaneBorder=Yellow\n\
@@ -265,6 +671,7 @@ impl Config {
             [Panels]\n\
             Left={}\n\
             Right={}\n\
+            Split={}\n\
             \n\
             [General]\n\
             ShowHidden={}\n\
@@ -278,6 +685,7 @@ impl Config {
             File={}\n",
             self.panels.left.display(),
             self.panels.right.display(),
+            self.panels.split,
             self.general.show_hidden,
             self.general.confirm_delete,
             self.general.confirm_overwrite,
@@ -317,9 +725,7 @@ fn parse_ini(content: &str) -> Result<HashMap<String, HashMap<String, String>>>
     Ok(sections)
 }
 
-fn parse_keybindings(section: &HashMap<String, String>) -> Result<Keybindings> {
-    let mut keybindings = Keybindings::default();
-    
+fn parse_keybindings(section: &HashMap<String, String>, keybindings: &mut Keybindings) -> Result<()> {
     for (key, value) in section {
         let binding = parse_key_binding(value)?;
         match key.as_str() {
@@ -337,11 +743,18 @@ fn parse_keybindings(section: &HashMap<String, String>) -> Result<Keybindings> {
             "Wildcard" => keybindings.wildcard = binding,
             "Reload" => keybindings.reload = binding,
             "SwitchPane" => keybindings.switch_pane = binding,
+            "ClipboardCopy" => keybindings.clipboard_copy = binding,
+            "ClipboardCut" => keybindings.clipboard_cut = binding,
+            "ClipboardPaste" => keybindings.clipboard_paste = binding,
+            "Info" => keybindings.info = binding,
+            "HotPath" => keybindings.hotpath = binding,
+            "Compare" => keybindings.compare = binding,
+            "PrevDir" => keybindings.prev_dir = binding,
             _ => log::warn!("Unknown keybinding: {}", key),
         }
     }
     
-    Ok(keybindings)
+    Ok(())
 }
 
 fn parse_key_binding(value: &str) -> Result<KeyBinding> {
@@ -397,9 +810,7 @@ fn parse_key_binding(value: &str) -> Result<KeyBinding> {
     Ok(KeyBinding::new(code, modifiers))
 }
 
-fn parse_colors(section: &HashMap<String, String>) -> Result<ColorScheme> {
-    let mut colors = ColorScheme::default();
-    
+fn parse_colors(section: &HashMap<String, String>, colors: &mut ColorScheme) -> Result<()> {
     for (key, value) in section {
         let color = parse_color(value)?;
         match key.as_str() {
@@ -414,7 +825,7 @@ fn parse_colors(section: &HashMap<String, String>) -> Result<ColorScheme> {
         }
     }
     
-    Ok(colors)
+    Ok(())
 }
 
 fn parse_color(value: &str) -> Result<Color> {
@@ -439,23 +850,30 @@ fn parse_color(value: &str) -> Result<Color> {
     }
 }
 
-fn parse_panels(section: &HashMap<String, String>) -> Result<PanelConfig> {
-    let mut panels = PanelConfig::default();
-    
+fn parse_panels(section: &HashMap<String, String>, panels: &mut PanelConfig) -> Result<()> {
     for (key, value) in section {
         match key.as_str() {
             "Left" => panels.left = PathBuf::from(value),
             "Right" => panels.right = PathBuf::from(value),
+            "Split" => {
+                let split: u16 = value.parse().map_err(|_| {
+                    GeekCommanderError::Config(format!("Invalid Split value: {}", value))
+                })?;
+                if !(10..=90).contains(&split) {
+                    return Err(GeekCommanderError::Config(format!(
+                        "Split must be between 10 and 90, got {}", split
+                    )));
+                }
+                panels.split = split;
+            },
             _ => log::warn!("Unknown panel setting: {}", key),
         }
     }
     
-    Ok(panels)
+    Ok(())
 }
 
-fn parse_general(section: &HashMap<String, String>) -> Result<GeneralConfig> {
-    let mut general = GeneralConfig::default();
-    
+fn parse_general(section: &HashMap<String, String>, general: &mut GeneralConfig) -> Result<()> {
     for (key, value) in section {
         match key.as_str() {
             "ShowHidden" => general.show_hidden = parse_bool(value)?,
@@ -463,25 +881,141 @@ fn parse_general(section: &HashMap<String, String>) -> Result<GeneralConfig> {
             "ConfirmOverwrite" => general.confirm_overwrite = parse_bool(value)?,
             "UseColors" => general.use_colors = parse_bool(value)?,
             "FollowSymlinks" => general.follow_symlinks = parse_bool(value)?,
+            "ShowLinkCount" => general.show_link_count = parse_bool(value)?,
+            "DirsFirst" => {
+                general.dirs_placement = match value.to_lowercase().as_str() {
+                    "true" | "yes" | "1" | "on" | "first" => DirsPlacement::First,
+                    "false" | "no" | "0" | "off" | "mixed" => DirsPlacement::Mixed,
+                    "last" => DirsPlacement::Last,
+                    _ => return Err(GeekCommanderError::Config(format!("Invalid DirsFirst value: {}", value))),
+                }
+            },
+            "CaseSensitivity" => {
+                general.case_sensitivity = match value.to_lowercase().as_str() {
+                    "sensitive" => CaseSensitivity::Sensitive,
+                    "insensitive" => CaseSensitivity::Insensitive,
+                    "smart" => CaseSensitivity::Smart,
+                    _ => return Err(GeekCommanderError::Config(format!("Invalid CaseSensitivity value: {}", value))),
+                }
+            },
+            "ShowDirSizes" => general.show_dir_sizes = parse_bool(value)?,
+            "ShowDirCounts" => general.show_dir_counts = parse_bool(value)?,
+            "ShowPermissions" => general.show_permissions = parse_bool(value)?,
+            "HighContrastSelection" => general.high_contrast_selection = parse_bool(value)?,
+            "AutoRefreshSeconds" => {
+                general.auto_refresh_secs = value.parse().map_err(|_| {
+                    GeekCommanderError::Config(format!("Invalid refresh interval: {}", value))
+                })?
+            },
+            "NavigationStyle" => {
+                general.navigation_style = match value.to_lowercase().as_str() {
+                    "classic" => NavigationStyle::Classic,
+                    "arrows" => NavigationStyle::Arrows,
+                    _ => return Err(GeekCommanderError::Config(format!("Invalid NavigationStyle value: {}", value))),
+                }
+            },
+            "NewDirMode" => {
+                general.new_dir_mode = Some(u32::from_str_radix(value, 8).map_err(|_| {
+                    GeekCommanderError::Config(format!("Invalid octal mode: {}", value))
+                })?)
+            },
             _ => log::warn!("Unknown general setting: {}", key),
         }
     }
     
-    Ok(general)
+    Ok(())
 }
 
-fn parse_logging(section: &HashMap<String, String>) -> Result<LoggingConfig> {
-    let mut logging = LoggingConfig::default();
-    
+fn parse_confirmation(section: &HashMap<String, String>, confirmation: &mut ConfirmationConfig) -> Result<()> {
+    for (key, value) in section {
+        match key.as_str() {
+            "Delete" => confirmation.delete = Some(parse_bool(value)?),
+            "Overwrite" => confirmation.overwrite = Some(parse_bool(value)?),
+            "Move" => confirmation.moves = parse_bool(value)?,
+            "BulkFiles" => {
+                confirmation.bulk_files = value.parse().map_err(|_| {
+                    GeekCommanderError::Config(format!("Invalid file count: {}", value))
+                })?
+            },
+            "BulkSizeMB" => {
+                confirmation.bulk_size_mb = value.parse().map_err(|_| {
+                    GeekCommanderError::Config(format!("Invalid size in MB: {}", value))
+                })?
+            },
+            "ExitWithJobs" => confirmation.exit_with_jobs = parse_bool(value)?,
+            _ => log::warn!("Unknown confirmation setting: {}", key),
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_logging(section: &HashMap<String, String>, logging: &mut LoggingConfig) -> Result<()> {
     for (key, value) in section {
         match key.as_str() {
             "Level" => logging.level = value.clone(),
             "File" => logging.file = PathBuf::from(value),
+            "AuditFile" => logging.audit_file = Some(PathBuf::from(value)),
             _ => log::warn!("Unknown logging setting: {}", key),
         }
     }
     
-    Ok(logging)
+    Ok(())
+}
+
+fn parse_enter_actions(section: &HashMap<String, String>, actions: &mut HashMap<String, EnterAction>) -> Result<()> {
+    for (extensions, value) in section {
+        let action = match value.trim().to_lowercase().as_str() {
+            "view" => EnterAction::View,
+            "edit" => EnterAction::Edit,
+            "open" => EnterAction::Open,
+            other => {
+                return Err(GeekCommanderError::Config(format!(
+                    "Invalid Enter action '{}' (expected view, edit or open)",
+                    other
+                )))
+            }
+        };
+        // Keys are extension groups like "jpg,png,gif"; a leading dot is tolerated
+        for ext in extensions.split(',').map(str::trim).filter(|ext| !ext.is_empty()) {
+            actions.insert(ext.trim_start_matches('.').to_lowercase(), action);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_viewer_limits(section: &HashMap<String, String>, limits: &mut HashMap<String, u64>) -> Result<()> {
+    for (extensions, value) in section {
+        let bytes = parse_size(value)?;
+        // Keys are extension groups like "log,txt"; a leading dot is
+        // tolerated and "default" replaces the built-in cap
+        for ext in extensions.split(',').map(str::trim).filter(|ext| !ext.is_empty()) {
+            limits.insert(ext.trim_start_matches('.').to_lowercase(), bytes);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a size like `500MB`, `2G` or a plain byte count
+fn parse_size(value: &str) -> Result<u64> {
+    let trimmed = value.trim();
+    let upper = trimmed.to_uppercase();
+    let (digits, multiplier) = if let Some(rest) = upper.strip_suffix("KB").or_else(|| upper.strip_suffix('K')) {
+        (rest, 1024)
+    } else if let Some(rest) = upper.strip_suffix("MB").or_else(|| upper.strip_suffix('M')) {
+        (rest, 1024 * 1024)
+    } else if let Some(rest) = upper.strip_suffix("GB").or_else(|| upper.strip_suffix('G')) {
+        (rest, 1024 * 1024 * 1024)
+    } else {
+        (upper.as_str(), 1)
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .map(|n| n * multiplier)
+        .map_err(|_| GeekCommanderError::Config(format!("Invalid size: {}", value)))
 }
 
 fn parse_bool(value: &str) -> Result<bool> {
@@ -559,6 +1093,103 @@ mod tests {
         assert_eq!(sections["Section2"]["Key4"], "Value4");
     }
 
+    #[test]
+    fn test_apply_override() {
+        let mut config = Config::default();
+
+        // Section and key names are matched case-insensitively, as they come
+        // from uppercase environment variable names
+        config.apply_override("GENERAL", "SHOWHIDDEN", "1").unwrap();
+        assert!(config.general.show_hidden);
+
+        config.apply_override("PANELS", "LEFT", "/srv").unwrap();
+        assert_eq!(config.panels.left, PathBuf::from("/srv"));
+
+        config.apply_override("PANELS", "SPLIT", "65").unwrap();
+        assert_eq!(config.panels.split, 65);
+        assert!(config.apply_override("PANELS", "SPLIT", "5").is_err());
+
+        config.apply_override("KEYBINDINGS", "QUIT", "Ctrl+Q").unwrap();
+        assert_eq!(config.keybindings.quit.code, KeyCode::Char('Q'));
+        assert_eq!(config.keybindings.quit.modifiers, KeyModifiers::CONTROL);
+
+        assert!(config.apply_override("NOSUCH", "KEY", "x").is_err());
+        assert!(config.apply_override("GENERAL", "NOSUCH", "x").is_err());
+        assert!(config.apply_override("GENERAL", "SHOWHIDDEN", "maybe").is_err());
+    }
+
+    #[test]
+    fn test_apply_content_validated() {
+        let content = "Orphan=1\n[General]\nShowHidden=true\nNoSuchKey=1\nnot a pair\n[Colors]\nActivePaneBorder=NotAColor\n";
+
+        let mut config = Config::default();
+        let problems = config.apply_content_validated(content);
+
+        // Every broken line is reported, with its line number
+        assert_eq!(problems.len(), 4);
+        assert!(problems[0].starts_with("line 1:"));
+        assert!(problems[1].starts_with("line 4:"));
+        assert!(problems[2].starts_with("line 5:"));
+        assert!(problems[3].starts_with("line 7:"));
+
+        // Valid lines were still applied; invalid ones kept their defaults
+        assert!(config.general.show_hidden);
+        assert_eq!(config.colors.active_pane_border, Color::Cyan);
+    }
+
+    #[test]
+    fn test_parse_confirmation() {
+        let mut config = Config::default();
+
+        // The legacy [General] flags apply until [Confirmation] overrides them
+        assert!(config.confirm_delete());
+        config.general.confirm_delete = false;
+        assert!(!config.confirm_delete());
+        config.apply_override("CONFIRMATION", "DELETE", "true").unwrap();
+        assert!(config.confirm_delete());
+
+        config.apply_override("CONFIRMATION", "MOVE", "false").unwrap();
+        assert!(!config.confirmation.moves);
+
+        config.apply_override("CONFIRMATION", "BULKFILES", "100").unwrap();
+        assert_eq!(config.confirmation.bulk_files, 100);
+        config.apply_override("CONFIRMATION", "BULKSIZEMB", "2048").unwrap();
+        assert_eq!(config.confirmation.bulk_size_mb, 2048);
+        assert!(config.apply_override("CONFIRMATION", "BULKFILES", "lots").is_err());
+
+        config.apply_override("CONFIRMATION", "EXITWITHJOBS", "false").unwrap();
+        assert!(!config.confirmation.exit_with_jobs);
+    }
+
+    #[test]
+    fn test_parse_enter_actions() {
+        let mut config = Config::default();
+
+        config.apply_override("EnterActions", "jpg,png,.gif", "open").unwrap();
+        config.apply_override("EnterActions", "md", "Edit").unwrap();
+        assert_eq!(config.enter_actions.get("jpg"), Some(&EnterAction::Open));
+        assert_eq!(config.enter_actions.get("gif"), Some(&EnterAction::Open));
+        assert_eq!(config.enter_actions.get("md"), Some(&EnterAction::Edit));
+        assert_eq!(config.enter_actions.get("txt"), None);
+
+        assert!(config.apply_override("EnterActions", "exe", "launch").is_err());
+    }
+
+    #[test]
+    fn test_parse_viewer_limits() {
+        let mut config = Config::default();
+
+        config.apply_override("ViewerLimits", "log,txt", "500MB").unwrap();
+        config.apply_override("ViewerLimits", ".bin", "64K").unwrap();
+        config.apply_override("ViewerLimits", "default", "1048576").unwrap();
+        assert_eq!(config.viewer_limits.get("log"), Some(&(500 * 1024 * 1024)));
+        assert_eq!(config.viewer_limits.get("txt"), Some(&(500 * 1024 * 1024)));
+        assert_eq!(config.viewer_limits.get("bin"), Some(&(64 * 1024)));
+        assert_eq!(config.viewer_limits.get("default"), Some(&1048576));
+
+        assert!(config.apply_override("ViewerLimits", "iso", "big").is_err());
+    }
+
     #[test]
     fn test_config_default() {
         let config = Config::default();
@@ -567,4 +1198,36 @@ mod tests {
         assert_eq!(config.general.show_hidden, false);
         assert_eq!(config.general.confirm_delete, true);
     }
-} 
\ No newline at end of file
+
+    /// Property tests: arbitrary config content must never panic the INI or
+    /// key-binding parsers, only produce values or errors.
+    mod parser_properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn parse_ini_never_panics(content in "\\PC*") {
+                let _ = parse_ini(&content);
+            }
+
+            #[test]
+            fn parse_key_binding_never_panics(value in "\\PC*") {
+                let _ = parse_key_binding(&value);
+            }
+
+            #[test]
+            fn function_keys_round_trip(n in 1u8..=12) {
+                let binding = parse_key_binding(&format!("F{}", n)).unwrap();
+                prop_assert_eq!(binding.code, KeyCode::F(n));
+                prop_assert_eq!(binding.modifiers, KeyModifiers::NONE);
+            }
+
+            #[test]
+            fn apply_content_never_panics(content in "\\PC*") {
+                let mut config = Config::default();
+                let _ = config.apply_content_validated(&content);
+            }
+        }
+    }
+}
@@ -1,20 +1,33 @@
 use std::fs::{self, DirEntry, File, Metadata};
-use std::io::{Read, Write};
+use std::io::{Read, Seek, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use crate::error::{GeekCommanderError, Result};
 use crate::platform;
 
 #[derive(Debug, Clone)]
 pub struct FileEntry {
+    /// Display name; invalid UTF-8 is rendered with replacement characters
     pub name: String,
+    /// The filename exactly as stored on disk, kept so operations still
+    /// match files whose names are not valid UTF-8
+    pub os_name: std::ffi::OsString,
     pub path: PathBuf,
     pub is_dir: bool,
     pub is_archive: bool,
     pub size: u64,
     pub modified: SystemTime,
     pub permissions: String,
+    /// Hard link count (always 1 on platforms without link metadata)
+    pub nlink: u64,
+    /// Recursive size for directories, filled in by the background scanner
+    /// when `ShowDirSizes` is enabled
+    pub dir_size: Option<u64>,
+    /// Number of immediate children for directories, filled in by the
+    /// background scanner when `ShowDirCounts` is enabled
+    pub child_count: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +39,46 @@ pub struct ArchiveEntry {
     pub modified: SystemTime,
 }
 
+/// Where directories sort relative to files in a pane listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirsPlacement {
+    #[default]
+    First,
+    Mixed,
+    Last,
+}
+
+/// Which column orders a pane listing. Directory grouping and the `..`
+/// entry are unaffected; the column only reorders within each group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortColumn {
+    #[default]
+    Name,
+    Size,
+    Modified,
+}
+
+/// How pattern matching (glob selection, filters) treats letter case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseSensitivity {
+    #[default]
+    Sensitive,
+    Insensitive,
+    /// Case-insensitive unless the pattern contains an uppercase letter
+    Smart,
+}
+
+impl CaseSensitivity {
+    /// Whether a given pattern should match case-sensitively under this mode
+    pub fn is_sensitive(&self, pattern: &str) -> bool {
+        match self {
+            CaseSensitivity::Sensitive => true,
+            CaseSensitivity::Insensitive => false,
+            CaseSensitivity::Smart => pattern.chars().any(|c| c.is_uppercase()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PaneState {
     pub current_path: PathBuf,
@@ -34,6 +87,34 @@ pub struct PaneState {
     pub scroll_offset: usize,
     pub selected_indices: HashSet<usize>,
     pub archive_context: Option<ArchiveContext>,
+    pub dirs_placement: DirsPlacement,
+    pub case_sensitivity: CaseSensitivity,
+    /// LRU of directory → entry name the cursor was on, so revisiting a
+    /// directory restores the cursor instead of jumping to the top
+    cursor_memory: Vec<(PathBuf, String)>,
+    /// Names that appeared in the last refresh, with when their transient
+    /// highlight expires
+    recent_entries: HashMap<String, std::time::Instant>,
+    /// Directory the entries were last read from, so a refresh can tell
+    /// "new file appeared" apart from "navigated somewhere else"
+    last_refresh_path: Option<PathBuf>,
+    /// Directory the pane was in before the current one, for the
+    /// `cd -` style toggle
+    previous_path: Option<PathBuf>,
+    /// Modification time of `current_path` when the entries were last read,
+    /// so the periodic auto-refresh can skip unchanged directories
+    dir_mtime: Option<SystemTime>,
+    /// List every file under the current directory recursively, with
+    /// subdirectory entries shown by their relative paths. Operations and
+    /// selections act on the real files behind those paths
+    pub flat_view: bool,
+    /// Column the listing is ordered by
+    pub sort_column: SortColumn,
+    /// Reverse the order within each directory group
+    pub sort_reversed: bool,
+    /// The initial directory read is still running on a background thread;
+    /// the pane renders a spinner instead of entries until it lands
+    pub loading: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -43,97 +124,237 @@ pub struct ArchiveContext {
     pub entries: Vec<ArchiveEntry>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct FileOperation {
     pub operation_type: OperationType,
     pub source_files: Vec<PathBuf>,
     pub destination: PathBuf,
     pub total_size: u64,
     pub processed_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub current_file: Option<String>,
+    /// Number of files involved, filled in by the pre-scan (0 = unknown)
+    pub files_total: u64,
+    pub files_completed: u64,
+    /// Size and progress of the file currently being processed, for the
+    /// secondary per-file progress bar
+    pub current_file_size: u64,
+    pub current_file_processed: u64,
     pub completed: bool,
     pub cancelled: bool,
+    pub exclude_patterns: Vec<String>,
+    /// Copy symlink targets instead of recreating the links themselves
+    pub dereference_symlinks: bool,
+    /// Skip destination files that already exist with the source's full size
+    /// (set when resuming a persisted operation after a restart)
+    #[serde(default)]
+    pub resume: bool,
+    /// JSONL file to append one record per touched file to, when auditing
+    /// is enabled via [Logging] AuditFile
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub audit_file: Option<PathBuf>,
+    /// Non-fatal problems noticed while running (e.g. a source changing
+    /// under the copy), reported in a summary once the operation finishes
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub warnings: Vec<String>,
+    /// Sources whose copies may not be faithful (changed or deleted while
+    /// being copied); a move keeps these instead of deleting the only
+    /// version that is known to be complete
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unreliable_sources: Vec<PathBuf>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum OperationType {
     Copy,
     Move,
     Delete,
 }
 
+/// How long newly appeared entries keep their highlight after a refresh
+const NEW_ENTRY_HIGHLIGHT: std::time::Duration = std::time::Duration::from_secs(5);
+
 impl PaneState {
     pub fn new(path: PathBuf) -> Result<Self> {
-        let mut state = PaneState {
+        let mut state = Self::new_loading(path);
+        state.refresh()?;
+        Ok(state)
+    }
+
+    /// A pane that has not read its directory yet. Startup uses this so the
+    /// UI can appear immediately and fill the listing in from a background
+    /// thread; `refresh` clears the loading flag.
+    pub fn new_loading(path: PathBuf) -> Self {
+        PaneState {
             current_path: path,
             entries: Vec::new(),
             cursor_index: 0,
             scroll_offset: 0,
             selected_indices: HashSet::new(),
             archive_context: None,
-        };
-        state.refresh()?;
-        Ok(state)
+            dirs_placement: DirsPlacement::default(),
+            case_sensitivity: CaseSensitivity::default(),
+            cursor_memory: Vec::new(),
+            recent_entries: HashMap::new(),
+            last_refresh_path: None,
+            previous_path: None,
+            dir_mtime: None,
+            flat_view: false,
+            sort_column: SortColumn::default(),
+            sort_reversed: false,
+            loading: true,
+        }
     }
 
     pub fn refresh(&mut self) -> Result<()> {
+        // Re-reading the same directory highlights entries that appeared
+        // since last time; navigating somewhere else highlights nothing
+        let same_directory = self.last_refresh_path.as_deref() == Some(self.current_path.as_path());
+        let known_names: HashSet<String> = if same_directory {
+            self.entries.iter().map(|e| e.name.clone()).collect()
+        } else {
+            self.recent_entries.clear();
+            HashSet::new()
+        };
+
         self.entries.clear();
-        
+
         // Add parent directory entry if not at root
         if let Some(parent) = self.current_path.parent() {
             if parent != self.current_path {
                 self.entries.push(FileEntry {
                     name: "..".to_string(),
+                    os_name: std::ffi::OsString::from(".."),
                     path: parent.to_path_buf(),
                     is_dir: true,
                     is_archive: false,
                     size: 0,
                     modified: SystemTime::UNIX_EPOCH,
                     permissions: "drwxrwxrwx".to_string(),
+                    nlink: 1,
+                    dir_size: None,
+                    child_count: None,
                 });
             }
         }
 
-        // Read directory contents
-        let read_dir = fs::read_dir(&self.current_path)
-            .map_err(|e| GeekCommanderError::Io(e))?;
+        // Sampled before the read so a change racing the listing still
+        // triggers the next periodic refresh
+        self.dir_mtime = fs::metadata(&self.current_path)
+            .ok()
+            .and_then(|m| m.modified().ok());
+
+        if self.flat_view {
+            // Every file in the tree, named by its path relative to the
+            // current directory; unreadable entries are simply left out
+            for entry in walkdir::WalkDir::new(&self.current_path).min_depth(1) {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(_) => continue,
+                };
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(_) => continue,
+                };
+                let path = entry.path().to_path_buf();
+                let name = path
+                    .strip_prefix(&self.current_path)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string();
 
-        for entry in read_dir {
-            let entry = entry.map_err(|e| GeekCommanderError::Io(e))?;
-            let path = entry.path();
-            let metadata = entry.metadata().map_err(|e| GeekCommanderError::Io(e))?;
-            
-            let name = entry.file_name().to_string_lossy().to_string();
-            let is_archive = is_supported_archive(&path);
-            
-            let file_entry = FileEntry {
-                name: name.clone(),
-                path: path.clone(),
-                is_dir: metadata.is_dir(),
-                is_archive,
-                size: metadata.len(),
-                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
-                permissions: platform::get_file_permissions(&metadata),
-            };
-            
-            self.entries.push(file_entry);
+                self.entries.push(FileEntry {
+                    name,
+                    os_name: entry.file_name().to_os_string(),
+                    is_dir: false,
+                    is_archive: is_supported_archive(&path),
+                    size: metadata.len(),
+                    modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    permissions: platform::get_file_permissions(&metadata),
+                    nlink: platform::get_link_count(&metadata),
+                    dir_size: None,
+                    child_count: None,
+                    path,
+                });
+            }
+        } else {
+            // Read directory contents
+            let read_dir = fs::read_dir(&self.current_path)
+                .map_err(|e| GeekCommanderError::Io(e))?;
+
+            for entry in read_dir {
+                let entry = entry.map_err(|e| GeekCommanderError::Io(e))?;
+                let path = entry.path();
+                let metadata = entry.metadata().map_err(|e| GeekCommanderError::Io(e))?;
+
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_archive = is_supported_archive(&path);
+
+                let file_entry = FileEntry {
+                    name: name.clone(),
+                    os_name: entry.file_name(),
+                    path: path.clone(),
+                    is_dir: metadata.is_dir(),
+                    is_archive,
+                    size: metadata.len(),
+                    modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    permissions: platform::get_file_permissions(&metadata),
+                    nlink: platform::get_link_count(&metadata),
+                    dir_size: None,
+                    child_count: None,
+                };
+
+                self.entries.push(file_entry);
+            }
         }
 
-        // Sort entries: directories first, then files, alphabetically
+        // Sort entries: ".." always first, then directories grouped according
+        // to the configured placement, then by the active sort column within
+        // each group (name as the tiebreaker)
+        let dirs_placement = self.dirs_placement;
+        let sort_column = self.sort_column;
+        let sort_reversed = self.sort_reversed;
         self.entries.sort_by(|a, b| {
             if a.name == ".." {
                 std::cmp::Ordering::Less
             } else if b.name == ".." {
                 std::cmp::Ordering::Greater
-            } else if a.is_dir && !b.is_dir {
-                std::cmp::Ordering::Less
-            } else if !a.is_dir && b.is_dir {
-                std::cmp::Ordering::Greater
             } else {
-                a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                let group_order = match dirs_placement {
+                    DirsPlacement::First => b.is_dir.cmp(&a.is_dir),
+                    DirsPlacement::Last => a.is_dir.cmp(&b.is_dir),
+                    DirsPlacement::Mixed => std::cmp::Ordering::Equal,
+                };
+                let mut column_order = match sort_column {
+                    SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                    SortColumn::Size => a.size.cmp(&b.size)
+                        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+                    SortColumn::Modified => a.modified.cmp(&b.modified)
+                        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+                };
+                if sort_reversed {
+                    column_order = column_order.reverse();
+                }
+                group_order.then(column_order)
             }
         });
 
+        // Mark entries that were not there before and drop expired highlights
+        let now = std::time::Instant::now();
+        if same_directory {
+            for entry in &self.entries {
+                if entry.name != ".." && !known_names.contains(&entry.name) {
+                    self.recent_entries
+                        .insert(entry.name.clone(), now + NEW_ENTRY_HIGHLIGHT);
+                }
+            }
+        }
+        self.recent_entries.retain(|_, expires| *expires > now);
+        self.last_refresh_path = Some(self.current_path.clone());
+
         // Reset cursor if needed
         if self.cursor_index >= self.entries.len() {
             self.cursor_index = 0;
@@ -142,9 +363,20 @@ impl PaneState {
         // Clear selections that are no longer valid
         self.selected_indices.retain(|&i| i < self.entries.len());
 
+        self.loading = false;
+
         Ok(())
     }
 
+    /// Whether `name` appeared recently enough to still carry the
+    /// "new item" highlight
+    pub fn is_recent_entry(&self, name: &str) -> bool {
+        self.recent_entries
+            .get(name)
+            .map(|expires| *expires > std::time::Instant::now())
+            .unwrap_or(false)
+    }
+
     /// Move cursor up by one position
     pub fn cursor_up(&mut self, _viewport_height: usize) {
         if self.cursor_index > 0 {
@@ -181,17 +413,104 @@ impl PaneState {
         self.cursor_index = self.entries.len().saturating_sub(1);
     }
 
+    /// Re-read the directory only when its modification time moved since the
+    /// last read. This is the change detection behind the periodic refresh
+    /// for filesystems without working change notification; returns whether
+    /// a refresh actually happened.
+    pub fn refresh_if_changed(&mut self) -> Result<bool> {
+        if self.archive_context.is_some() {
+            return Ok(false);
+        }
+        let current = fs::metadata(&self.current_path)
+            .ok()
+            .and_then(|m| m.modified().ok());
+        if current.is_some() && current == self.dir_mtime {
+            return Ok(false);
+        }
+        self.refresh()?;
+        Ok(true)
+    }
+
+    /// Sort by `column`, or flip the direction when that column is already
+    /// active. The caller refreshes to apply the new order.
+    pub fn toggle_sort(&mut self, column: SortColumn) {
+        if self.sort_column == column {
+            self.sort_reversed = !self.sort_reversed;
+        } else {
+            self.sort_column = column;
+            self.sort_reversed = false;
+        }
+    }
+
+    /// Place the cursor on the entry named `name`, if present
+    pub fn focus_entry(&mut self, name: &str) -> bool {
+        match self.entries.iter().position(|e| e.name == name) {
+            Some(index) => {
+                self.cursor_index = index;
+                true
+            },
+            None => false,
+        }
+    }
+
     pub fn enter_directory(&mut self, new_path: PathBuf) -> Result<()> {
         if new_path.is_dir() {
-            self.current_path = new_path;
+            self.remember_cursor();
+            self.previous_path = Some(std::mem::replace(&mut self.current_path, new_path));
             self.cursor_index = 0;
             self.scroll_offset = 0;
             self.selected_indices.clear();
             self.refresh()?;
+            self.restore_cursor();
+        }
+        Ok(())
+    }
+
+    /// Swap between the current and previous directory, like `cd -`.
+    /// Entering the previous directory makes the current one the new
+    /// previous, so the toggle flips back and forth between the two.
+    pub fn toggle_previous_directory(&mut self) -> Result<()> {
+        if let Some(previous) = self.previous_path.clone() {
+            if previous.is_dir() {
+                self.enter_directory(previous)?;
+            }
         }
         Ok(())
     }
 
+    /// Record which entry the cursor is on in the current directory
+    fn remember_cursor(&mut self) {
+        const CURSOR_MEMORY_LIMIT: usize = 64;
+
+        let name = match self.get_current_entry() {
+            Some(entry) => entry.name.clone(),
+            None => return,
+        };
+
+        self.cursor_memory.retain(|(path, _)| path != &self.current_path);
+        self.cursor_memory.push((self.current_path.clone(), name));
+
+        // Drop the least recently visited directory once the map is full
+        if self.cursor_memory.len() > CURSOR_MEMORY_LIMIT {
+            self.cursor_memory.remove(0);
+        }
+    }
+
+    /// Put the cursor back on the remembered entry for the current directory,
+    /// if it still exists
+    fn restore_cursor(&mut self) {
+        let remembered = self.cursor_memory
+            .iter()
+            .find(|(path, _)| path == &self.current_path)
+            .map(|(_, name)| name.clone());
+
+        if let Some(name) = remembered {
+            if let Some(index) = self.entries.iter().position(|e| e.name == name) {
+                self.cursor_index = index;
+            }
+        }
+    }
+
     pub fn get_current_entry(&self) -> Option<&FileEntry> {
         self.entries.get(self.cursor_index)
     }
@@ -230,6 +549,29 @@ impl PaneState {
         !self.selected_indices.is_empty()
     }
 
+    /// Select entries whose name or full path appears in `names`, as read
+    /// from a list file produced by another tool. Returns how many entries
+    /// were newly selected.
+    pub fn select_named(&mut self, names: &[String]) -> usize {
+        let mut count = 0;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            if entry.name == ".." {
+                continue;
+            }
+
+            let matched = names.iter().any(|name| {
+                let name = name.trim();
+                !name.is_empty() && (name == entry.name || Path::new(name) == entry.path)
+            });
+            if matched && self.selected_indices.insert(i) {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
     pub fn select_by_pattern(&mut self, pattern: &str) -> Result<usize> {
         let mut count = 0;
         
@@ -238,7 +580,7 @@ impl PaneState {
                 continue;
             }
             
-            if matches_glob_pattern(&entry.name, pattern) {
+            if matches_glob_pattern_cased(&entry.name, pattern, self.case_sensitivity) {
                 self.selected_indices.insert(i);
                 count += 1;
             }
@@ -249,191 +591,1006 @@ impl PaneState {
 }
 
 pub fn copy_files(sources: &[&FileEntry], destination: &Path) -> Result<FileOperation> {
-    let total_size = calculate_total_size(sources)?;
+    copy_files_with_excludes(sources, destination, Vec::new())
+}
+
+pub fn copy_files_with_excludes(sources: &[&FileEntry], destination: &Path, exclude_patterns: Vec<String>) -> Result<FileOperation> {
     let source_paths: Vec<PathBuf> = sources.iter().map(|e| e.path.clone()).collect();
-    
+
+    // total_size starts at 0 (indeterminate); callers fill it in via the
+    // background pre-scan so big trees don't freeze the UI here.
     let operation = FileOperation {
         operation_type: OperationType::Copy,
         source_files: source_paths,
         destination: destination.to_path_buf(),
-        total_size,
+        total_size: 0,
         processed_size: 0,
         current_file: None,
+        files_total: 0,
+        files_completed: 0,
+        current_file_size: 0,
+        current_file_processed: 0,
         completed: false,
         cancelled: false,
+        dereference_symlinks: false,
+        resume: false,
+        audit_file: None,
+        warnings: Vec::new(),
+        unreliable_sources: Vec::new(),
+        exclude_patterns,
     };
-    
+
     Ok(operation)
 }
 
+/// Build a copy operation from raw paths (used by the internal clipboard,
+/// where sources may come from several different directories)
+pub fn copy_paths(sources: Vec<PathBuf>, destination: &Path) -> FileOperation {
+    FileOperation {
+        operation_type: OperationType::Copy,
+        source_files: sources,
+        destination: destination.to_path_buf(),
+        total_size: 0,
+        processed_size: 0,
+        current_file: None,
+        files_total: 0,
+        files_completed: 0,
+        current_file_size: 0,
+        current_file_processed: 0,
+        completed: false,
+        cancelled: false,
+        dereference_symlinks: false,
+        resume: false,
+        audit_file: None,
+        warnings: Vec::new(),
+        unreliable_sources: Vec::new(),
+        exclude_patterns: Vec::new(),
+    }
+}
+
+/// Build a move operation from raw paths (used by the internal clipboard)
+pub fn move_paths(sources: Vec<PathBuf>, destination: &Path) -> FileOperation {
+    FileOperation {
+        operation_type: OperationType::Move,
+        source_files: sources,
+        destination: destination.to_path_buf(),
+        total_size: 0,
+        processed_size: 0,
+        current_file: None,
+        files_total: 0,
+        files_completed: 0,
+        current_file_size: 0,
+        current_file_processed: 0,
+        completed: false,
+        cancelled: false,
+        dereference_symlinks: false,
+        resume: false,
+        audit_file: None,
+        warnings: Vec::new(),
+        unreliable_sources: Vec::new(),
+        exclude_patterns: Vec::new(),
+    }
+}
+
 pub fn move_files(sources: &[&FileEntry], destination: &Path) -> Result<FileOperation> {
-    let total_size = calculate_total_size(sources)?;
     let source_paths: Vec<PathBuf> = sources.iter().map(|e| e.path.clone()).collect();
-    
+
     let operation = FileOperation {
         operation_type: OperationType::Move,
         source_files: source_paths,
         destination: destination.to_path_buf(),
-        total_size,
+        total_size: 0,
         processed_size: 0,
         current_file: None,
+        files_total: 0,
+        files_completed: 0,
+        current_file_size: 0,
+        current_file_processed: 0,
         completed: false,
         cancelled: false,
+        dereference_symlinks: false,
+        resume: false,
+        audit_file: None,
+        warnings: Vec::new(),
+        unreliable_sources: Vec::new(),
+        exclude_patterns: Vec::new(),
     };
-    
+
     Ok(operation)
 }
 
 pub fn delete_files(sources: &[&FileEntry]) -> Result<FileOperation> {
-    let total_size = calculate_total_size(sources)?;
     let source_paths: Vec<PathBuf> = sources.iter().map(|e| e.path.clone()).collect();
-    
+
     let operation = FileOperation {
         operation_type: OperationType::Delete,
         source_files: source_paths,
         destination: PathBuf::new(),
-        total_size,
+        total_size: 0,
         processed_size: 0,
         current_file: None,
+        files_total: 0,
+        files_completed: 0,
+        current_file_size: 0,
+        current_file_processed: 0,
         completed: false,
         cancelled: false,
+        dereference_symlinks: false,
+        resume: false,
+        audit_file: None,
+        warnings: Vec::new(),
+        unreliable_sources: Vec::new(),
+        exclude_patterns: Vec::new(),
     };
-    
+
     Ok(operation)
 }
 
+/// Execute an operation synchronously, updating the progress fields in place.
+/// Interactive callers should prefer `spawn_operation` so the UI stays live.
 pub fn execute_operation(operation: &mut FileOperation) -> Result<()> {
-    match operation.operation_type {
+    let shared = Mutex::new(operation.clone());
+    let result = execute_operation_shared(&shared);
+    *operation = shared.into_inner().unwrap();
+    result
+}
+
+/// Run an operation on a background thread. The shared state is updated live
+/// so the UI can render progress, and setting `cancelled` on it requests a
+/// cancellation; the handle yields the final result once the worker finishes.
+pub fn spawn_operation(
+    operation: FileOperation,
+) -> (Arc<Mutex<FileOperation>>, std::thread::JoinHandle<Result<()>>) {
+    let shared = Arc::new(Mutex::new(operation));
+    let worker = Arc::clone(&shared);
+    let handle = std::thread::spawn(move || execute_operation_shared(&worker));
+    (shared, handle)
+}
+
+/// Persist an unfinished operation so it can be offered for resume on the
+/// next startup. Written crash-safely; cleared again once the operation
+/// completes.
+pub fn save_operation_state(path: &Path, operation: &FileOperation) -> Result<()> {
+    let content = toml::to_string(operation)
+        .map_err(|e| GeekCommanderError::Config(format!("Failed to serialize operation state: {}", e)))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    platform::atomic_write(path, &content)?;
+    Ok(())
+}
+
+/// Load a previously persisted operation, if any. An unreadable or corrupt
+/// state file is treated as absent.
+pub fn load_operation_state(path: &Path) -> Option<FileOperation> {
+    let content = fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Remove the persisted operation state after completion or decline
+pub fn clear_operation_state(path: &Path) {
+    let _ = fs::remove_file(path);
+}
+
+/// Persist the recent-files list, newest first, one path per line
+pub fn save_recent_files(path: &Path, files: &[PathBuf]) -> Result<()> {
+    let content: String = files
+        .iter()
+        .map(|file| format!("{}\n", file.display()))
+        .collect();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    platform::atomic_write(path, &content)?;
+    Ok(())
+}
+
+/// Load the persisted recent-files list. A missing or unreadable file just
+/// means an empty history; entries that no longer exist are dropped here
+/// rather than cluttering the overlay.
+pub fn load_recent_files(path: &Path) -> Vec<PathBuf> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .filter(|file| file.exists())
+        .collect()
+}
+
+/// Append a JSONL record describing one touched file to the operation's
+/// audit log, when one is configured. Audit failures are logged but never
+/// fail the operation itself.
+fn audit_record(operation: &Mutex<FileOperation>, source: &Path, destination: Option<&Path>, size: u64, result: &Result<()>) {
+    let (audit_file, action) = {
+        let op = operation.lock().unwrap();
+        let action = match op.operation_type {
+            OperationType::Copy => "copy",
+            OperationType::Move => "move",
+            OperationType::Delete => "delete",
+        };
+        match &op.audit_file {
+            Some(path) => (path.clone(), action),
+            None => return,
+        }
+    };
+
+    let outcome = match result {
+        Ok(()) => "ok".to_string(),
+        Err(e) => e.to_string(),
+    };
+    let mut record = format!(
+        "{{\"time\":\"{}\",\"action\":\"{}\",\"source\":\"{}\",",
+        chrono::Local::now().to_rfc3339(),
+        action,
+        json_escape(&source.to_string_lossy()),
+    );
+    if let Some(dest) = destination {
+        record.push_str(&format!("\"destination\":\"{}\",", json_escape(&dest.to_string_lossy())));
+    }
+    record.push_str(&format!("\"size\":{},\"result\":\"{}\"}}", size, json_escape(&outcome)));
+
+    let appended = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&audit_file)
+        .and_then(|mut file| writeln!(file, "{}", record));
+    if let Err(e) = appended {
+        log::warn!("Failed to write audit record to {}: {}", audit_file.display(), e);
+    }
+}
+
+/// Escape a string for embedding in a JSON string literal
+fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn execute_operation_shared(operation: &Mutex<FileOperation>) -> Result<()> {
+    let operation_type = operation.lock().unwrap().operation_type.clone();
+    match operation_type {
         OperationType::Copy => execute_copy_operation(operation),
         OperationType::Move => execute_move_operation(operation),
         OperationType::Delete => execute_delete_operation(operation),
     }
 }
 
-fn execute_copy_operation(operation: &mut FileOperation) -> Result<()> {
-    let source_files = operation.source_files.clone(); // Clone to avoid borrowing issues
-    
+/// Reject copying a directory into itself or one of its subdirectories,
+/// which would otherwise recurse until the disk fills up
+pub fn check_copy_into_self(sources: &[PathBuf], destination: &Path) -> Result<()> {
+    let dest = destination.canonicalize().unwrap_or_else(|_| destination.to_path_buf());
+    for source in sources {
+        if !source.is_dir() {
+            continue;
+        }
+        let source = source.canonicalize().unwrap_or_else(|_| source.clone());
+        if dest.starts_with(&source) {
+            return Err(GeekCommanderError::FileOperation(format!(
+                "Cannot copy '{}' into itself",
+                source.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn execute_copy_operation(operation: &Mutex<FileOperation>) -> Result<()> {
+    let (source_files, exclude_patterns, destination, dereference) = {
+        let op = operation.lock().unwrap();
+        (op.source_files.clone(), op.exclude_patterns.clone(), op.destination.clone(), op.dereference_symlinks)
+    };
+
+    check_copy_into_self(&source_files, &destination)?;
+
     for source_path in &source_files {
-        if operation.cancelled {
+        if operation.lock().unwrap().cancelled {
             break;
         }
-        
+
         let file_name = source_path.file_name()
             .ok_or_else(|| GeekCommanderError::FileOperation("Invalid source file name".to_string()))?
             .to_string_lossy();
-        
-        operation.current_file = Some(file_name.to_string());
-        
-        let dest_path = operation.destination.join(&*file_name);
-        
-        if source_path.is_dir() {
+
+        operation.lock().unwrap().current_file = Some(file_name.to_string());
+
+        let dest_path = destination.join(&*file_name);
+
+        if is_excluded(&file_name, source_path.is_dir(), &exclude_patterns) {
+            continue;
+        }
+
+        if !dereference && is_symlink(source_path) {
+            let result = copy_symlink(source_path, &dest_path);
+            audit_record(operation, source_path, Some(&dest_path), 0, &result);
+            result?;
+            operation.lock().unwrap().files_completed += 1;
+        } else if source_path.is_dir() {
             copy_directory_recursive(source_path, &dest_path, operation)?;
         } else {
-            copy_file_with_progress(source_path, &dest_path, operation)?;
+            let size = fs::metadata(source_path).map(|m| m.len()).unwrap_or(0);
+            let result = copy_file_with_progress(source_path, &dest_path, operation);
+            audit_record(operation, source_path, Some(&dest_path), size, &result);
+            result?;
         }
     }
-    
-    operation.completed = true;
+
+    operation.lock().unwrap().completed = true;
     Ok(())
 }
 
-fn execute_move_operation(operation: &mut FileOperation) -> Result<()> {
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// Recreate a symlink at the destination instead of copying its target
+fn copy_symlink(source: &Path, dest: &Path) -> Result<()> {
+    let target = fs::read_link(source)?;
+
+    // Replace a stale entry at the destination, if any
+    if fs::symlink_metadata(dest).is_ok() {
+        let _ = fs::remove_file(dest);
+    }
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, dest)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        if source.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, dest)?;
+        } else {
+            std::os::windows::fs::symlink_file(&target, dest)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = target;
+        Err(GeekCommanderError::FileOperation(
+            "Symlinks are not supported on this platform".to_string(),
+        ))
+    }
+}
+
+fn execute_move_operation(operation: &Mutex<FileOperation>) -> Result<()> {
     // First copy all files, then delete originals
     execute_copy_operation(operation)?;
-    
-    if !operation.cancelled {
-        for source_path in &operation.source_files {
-            if source_path.is_dir() {
-                fs::remove_dir_all(source_path)?;
-            } else {
-                fs::remove_file(source_path)?;
-            }
+
+    let (cancelled, source_files, exclude_patterns, unreliable_sources) = {
+        let op = operation.lock().unwrap();
+        (
+            op.cancelled,
+            op.source_files.clone(),
+            op.exclude_patterns.clone(),
+            op.unreliable_sources.clone(),
+        )
+    };
+    if cancelled {
+        return Ok(());
+    }
+
+    for source_path in &source_files {
+        // A source whose copy may not be faithful (changed or deleted while
+        // it was being copied) is the only complete version left — keep it
+        if unreliable_sources.iter().any(|flagged| flagged.starts_with(source_path)) {
+            operation.lock().unwrap().warnings.push(format!(
+                "'{}' was not deleted; its copy may not be complete",
+                source_path.display()
+            ));
+            continue;
+        }
+
+        // Excluded entries were never copied, so removing them (or a whole
+        // tree that still holds them) would destroy data. Not reachable from
+        // the UI today, but the library API and resume state allow it.
+        let file_name = source_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if is_excluded(&file_name, source_path.is_dir(), &exclude_patterns) {
+            continue;
+        }
+        if source_path.is_dir() && !exclude_patterns.is_empty() {
+            operation.lock().unwrap().warnings.push(format!(
+                "'{}' was kept; exclude patterns may have left some of its contents uncopied",
+                source_path.display()
+            ));
+            continue;
+        }
+
+        if source_path.is_dir() {
+            fs::remove_dir_all(source_path)?;
+        } else {
+            fs::remove_file(source_path)?;
         }
     }
-    
+
     Ok(())
 }
 
-fn execute_delete_operation(operation: &mut FileOperation) -> Result<()> {
-    for source_path in &operation.source_files {
-        if operation.cancelled {
+fn execute_delete_operation(operation: &Mutex<FileOperation>) -> Result<()> {
+    let source_files = operation.lock().unwrap().source_files.clone();
+
+    for source_path in &source_files {
+        if operation.lock().unwrap().cancelled {
             break;
         }
-        
+
         let file_name = source_path.file_name()
             .ok_or_else(|| GeekCommanderError::FileOperation("Invalid source file name".to_string()))?
             .to_string_lossy();
-        
-        operation.current_file = Some(file_name.to_string());
-        
-        if source_path.is_dir() {
-            fs::remove_dir_all(source_path)?;
+
+        operation.lock().unwrap().current_file = Some(file_name.to_string());
+
+        // Size has to be taken before the entry disappears
+        let size = get_path_size(source_path)?;
+
+        let result = if source_path.is_dir() {
+            fs::remove_dir_all(source_path).map_err(GeekCommanderError::from)
         } else {
-            fs::remove_file(source_path)?;
-        }
-        
-        operation.processed_size += get_path_size(source_path)?;
+            fs::remove_file(source_path).map_err(GeekCommanderError::from)
+        };
+        audit_record(operation, source_path, None, size, &result);
+        result?;
+
+        let mut op = operation.lock().unwrap();
+        op.processed_size += size;
+        op.files_completed += 1;
     }
-    
-    operation.completed = true;
+
+    operation.lock().unwrap().completed = true;
     Ok(())
 }
 
-fn copy_file_with_progress(source: &Path, dest: &Path, operation: &mut FileOperation) -> Result<()> {
+/// How many times a copy is restarted when the source changes underneath it
+const COPY_RETRY_LIMIT: usize = 3;
+
+fn copy_file_with_progress(source: &Path, dest: &Path, operation: &Mutex<FileOperation>) -> Result<()> {
     if let Some(parent) = dest.parent() {
         fs::create_dir_all(parent)?;
     }
-    
-    let mut source_file = fs::File::open(source)?;
-    let mut dest_file = fs::File::create(dest)?;
-    
-    let mut buffer = vec![0u8; 64 * 1024]; // 64KB buffer
-    
-    loop {
-        if operation.cancelled {
-            break;
+
+    // When resuming an interrupted operation, files that already arrived in
+    // full are skipped; partially copied ones are rewritten from scratch
+    if operation.lock().unwrap().resume {
+        if let (Ok(source_meta), Ok(dest_meta)) = (fs::metadata(source), fs::metadata(dest)) {
+            if source_meta.len() == dest_meta.len() {
+                let mut op = operation.lock().unwrap();
+                op.processed_size += source_meta.len();
+                op.files_completed += 1;
+                return Ok(());
+            }
         }
-        
-        let bytes_read = source_file.read(&mut buffer)?;
-        if bytes_read == 0 {
+    }
+
+    let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    for attempt in 1..=COPY_RETRY_LIMIT {
+        let before = fs::metadata(source)?;
+        let written = copy_file_once(source, dest, operation)?;
+
+        if operation.lock().unwrap().cancelled {
             break;
         }
-        
-        dest_file.write_all(&buffer[..bytes_read])?;
-        operation.processed_size += bytes_read as u64;
+
+        // A source that was deleted or rewritten mid-copy would otherwise
+        // leave a silently truncated or mixed-content destination
+        match fs::metadata(source) {
+            Err(_) => {
+                let mut op = operation.lock().unwrap();
+                op.warnings.push(format!(
+                    "'{}' was deleted while it was being copied",
+                    name
+                ));
+                op.unreliable_sources.push(source.to_path_buf());
+                break;
+            }
+            Ok(after) => {
+                let changed = after.len() != before.len()
+                    || after.modified().ok() != before.modified().ok();
+                if !changed {
+                    break;
+                }
+                if attempt == COPY_RETRY_LIMIT {
+                    let mut op = operation.lock().unwrap();
+                    op.warnings.push(format!(
+                        "'{}' changed during copy; the destination may not match the source",
+                        name
+                    ));
+                    op.unreliable_sources.push(source.to_path_buf());
+                    break;
+                }
+                // Roll back this attempt's progress and re-read from scratch
+                let mut op = operation.lock().unwrap();
+                op.processed_size = op.processed_size.saturating_sub(written);
+            }
+        }
     }
-    
+
+    let cancelled = operation.lock().unwrap().cancelled;
+
+    // Carry the source permissions over instead of leaving the default mode
+    #[cfg(unix)]
+    if !cancelled {
+        if let Ok(metadata) = fs::metadata(source) {
+            let _ = fs::set_permissions(dest, metadata.permissions());
+        }
+    }
+
+    if !cancelled {
+        // Carry named NTFS streams over too, so Windows metadata like zone
+        // identifiers survives the copy (no-op elsewhere)
+        for stream in platform::copy_alternate_data_streams(source, dest) {
+            operation.lock().unwrap().warnings.push(format!(
+                "Could not preserve alternate data stream '{}:{}'",
+                name, stream
+            ));
+        }
+        operation.lock().unwrap().files_completed += 1;
+    }
+
     Ok(())
 }
 
-fn copy_directory_recursive(source: &Path, dest: &Path, operation: &mut FileOperation) -> Result<()> {
+/// One pass of the buffered copy loop. Returns how many bytes were written,
+/// so a retry can roll its contribution back out of the progress counters.
+fn copy_file_once(source: &Path, dest: &Path, operation: &Mutex<FileOperation>) -> Result<u64> {
+    let mut source_file = fs::File::open(source)?;
+    let mut dest_file = fs::File::create(dest)?;
+
+    // For sparse sources (VM disk images and the like), seek over zero
+    // blocks instead of writing them so the copy stays sparse
+    let sparse = platform::is_sparse_file(source);
+
+    {
+        let mut op = operation.lock().unwrap();
+        op.current_file = source.file_name().map(|n| n.to_string_lossy().to_string());
+        op.current_file_size = source_file.metadata().map(|m| m.len()).unwrap_or(0);
+        op.current_file_processed = 0;
+    }
+
+    let mut buffer = vec![0u8; 64 * 1024]; // 64KB buffer
+    let mut written = 0u64;
+
+    loop {
+        if operation.lock().unwrap().cancelled {
+            break;
+        }
+
+        let bytes_read = source_file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        if sparse && buffer[..bytes_read].iter().all(|&b| b == 0) {
+            dest_file.seek(std::io::SeekFrom::Current(bytes_read as i64))?;
+        } else {
+            dest_file.write_all(&buffer[..bytes_read])?;
+        }
+        written += bytes_read as u64;
+        let mut op = operation.lock().unwrap();
+        op.processed_size += bytes_read as u64;
+        op.current_file_processed += bytes_read as u64;
+    }
+
+    // Materialize any trailing hole the final seeks left behind
+    if sparse {
+        dest_file.set_len(written)?;
+    }
+
+    Ok(written)
+}
+
+fn copy_directory_recursive(source: &Path, dest: &Path, operation: &Mutex<FileOperation>) -> Result<()> {
     fs::create_dir_all(dest)?;
-    
+
+    #[cfg(unix)]
+    if let Ok(metadata) = fs::metadata(source) {
+        let _ = fs::set_permissions(dest, metadata.permissions());
+    }
+
+    let (exclude_patterns, dereference) = {
+        let op = operation.lock().unwrap();
+        (op.exclude_patterns.clone(), op.dereference_symlinks)
+    };
+
     for entry in fs::read_dir(source)? {
-        if operation.cancelled {
+        if operation.lock().unwrap().cancelled {
             break;
         }
-        
+
         let entry = entry?;
         let source_path = entry.path();
         let dest_path = dest.join(entry.file_name());
-        
-        if source_path.is_dir() {
+
+        let entry_name = entry.file_name().to_string_lossy().to_string();
+        if is_excluded(&entry_name, source_path.is_dir(), &exclude_patterns) {
+            continue;
+        }
+
+        if !dereference && is_symlink(&source_path) {
+            let result = copy_symlink(&source_path, &dest_path);
+            audit_record(operation, &source_path, Some(&dest_path), 0, &result);
+            result?;
+            operation.lock().unwrap().files_completed += 1;
+        } else if source_path.is_dir() {
             copy_directory_recursive(&source_path, &dest_path, operation)?;
         } else {
-            copy_file_with_progress(&source_path, &dest_path, operation)?;
+            let size = fs::metadata(&source_path).map(|m| m.len()).unwrap_or(0);
+            let result = copy_file_with_progress(&source_path, &dest_path, operation);
+            audit_record(operation, &source_path, Some(&dest_path), size, &result);
+            result?;
         }
     }
-    
+
     Ok(())
 }
 
-fn calculate_total_size(sources: &[&FileEntry]) -> Result<u64> {
-    let mut total = 0;
-    for entry in sources {
-        total += get_path_size(&entry.path)?;
+/// Walk the given paths on a background thread, streaming running
+/// (file count, total size) pairs through the returned channel. The final
+/// totals are sent just before the sender is dropped, so a disconnected
+/// receiver has seen the complete values.
+pub fn scan_total_size_background(paths: Vec<PathBuf>) -> std::sync::mpsc::Receiver<(u64, u64)> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let mut total = 0u64;
+        let mut files_seen = 0u64;
+        for path in &paths {
+            let _ = scan_path_size(path, &mut total, &mut files_seen, &tx);
+        }
+        let _ = tx.send((files_seen, total));
+    });
+
+    rx
+}
+
+/// Compute the recursive size of each directory on a background thread,
+/// streaming (directory, size) pairs through the returned channel. The
+/// scan stops between directories once `cancel` is set, so navigating
+/// away does not leave a stale walk running to completion.
+pub fn spawn_dir_size_scan(
+    dirs: Vec<PathBuf>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> std::sync::mpsc::Receiver<(PathBuf, u64)> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        for dir in dirs {
+            if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+            if let Ok(size) = get_path_size(&dir) {
+                if tx.send((dir, size)).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+/// Count the immediate children of each directory on a background thread,
+/// streaming (directory, count) pairs through the returned channel. A
+/// single `read_dir` per directory keeps this cheap, but slow network
+/// mounts still must not stall the UI thread.
+pub fn spawn_dir_count_scan(
+    dirs: Vec<PathBuf>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> std::sync::mpsc::Receiver<(PathBuf, u64)> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        for dir in dirs {
+            if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+            if let Ok(read_dir) = fs::read_dir(&dir) {
+                let count = read_dir.filter(|entry| entry.is_ok()).count() as u64;
+                if tx.send((dir, count)).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+/// Build a human-readable summary of a directory tree: totals, counts and
+/// sizes by extension, and a size-annotated listing. Shown in the viewer
+/// when F3 is pressed on a directory.
+pub fn build_directory_summary(path: &Path) -> String {
+    // Keep the listing bounded so huge trees stay viewable
+    const MAX_LISTED_ENTRIES: usize = 1000;
+
+    let mut listing = String::new();
+    let mut listed = 0usize;
+    let mut total_files = 0u64;
+    let mut total_dirs = 0u64;
+    let mut total_size = 0u64;
+    let mut by_extension: HashMap<String, (u64, u64)> = HashMap::new();
+
+    for entry in walkdir::WalkDir::new(path).min_depth(1).sort_by_file_name() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue, // Unreadable entries are simply left out
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = entry.file_type().is_dir();
+        let size = if is_dir { 0 } else { entry.metadata().map(|m| m.len()).unwrap_or(0) };
+
+        if is_dir {
+            total_dirs += 1;
+        } else {
+            total_files += 1;
+            total_size += size;
+            let extension = Path::new(&name)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_else(|| "(none)".to_string());
+            let slot = by_extension.entry(extension).or_insert((0, 0));
+            slot.0 += 1;
+            slot.1 += size;
+        }
+
+        if listed < MAX_LISTED_ENTRIES {
+            let indent = "  ".repeat(entry.depth().saturating_sub(1));
+            if is_dir {
+                listing.push_str(&format!("{}{}/\n", indent, name));
+            } else {
+                listing.push_str(&format!("{}{}  ({})\n", indent, name, platform::format_file_size(size)));
+            }
+            listed += 1;
+            if listed == MAX_LISTED_ENTRIES {
+                listing.push_str("... (listing truncated)\n");
+            }
+        }
+    }
+
+    // Most common extensions first, alphabetical between equals
+    let mut extensions: Vec<(String, (u64, u64))> = by_extension.into_iter().collect();
+    extensions.sort_by(|a, b| b.1.0.cmp(&a.1.0).then_with(|| a.0.cmp(&b.0)));
+
+    let mut summary = format!(
+        "Summary of {}\n{} directories, {} files, {} total\n\nFiles by extension:\n",
+        path.display(),
+        total_dirs,
+        total_files,
+        platform::format_file_size(total_size)
+    );
+    for (extension, (count, size)) in &extensions {
+        summary.push_str(&format!(
+            "  {:<12} {:>6} file(s)  {}\n",
+            extension,
+            count,
+            platform::format_file_size(*size)
+        ));
+    }
+    summary.push_str("\nTree:\n");
+    summary.push_str(&listing);
+    summary
+}
+
+/// List the files under `dir` that differ from `reference` in the
+/// containing git repository, as paths relative to `dir` (straight from
+/// `git diff --relative`). Untracked files count as changed too, so a
+/// fresh file makes it into the patchset. Not being in a repository, or a
+/// bad ref, surfaces git's own message.
+pub fn git_changed_files(dir: &Path, reference: &str) -> Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["diff", "--name-only", "--relative", reference, "--", "."])
+        .output()
+        .map_err(|e| GeekCommanderError::FileOperation(format!("Cannot run git: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GeekCommanderError::FileOperation(
+            stderr.lines().next().unwrap_or("git diff failed").to_string(),
+        ));
     }
-    Ok(total)
+
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect();
+
+    let untracked = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["ls-files", "--others", "--exclude-standard"])
+        .output();
+    if let Ok(output) = untracked {
+        if output.status.success() {
+            files.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.to_string()),
+            );
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Build a plain-text side-by-side report of both panel listings, for
+/// documentation or review before a large mirror operation
+pub fn build_panel_report(left: &PaneState, right: &PaneState) -> String {
+    // Wide enough for most names plus a size, narrow enough for a printout
+    const COLUMN_WIDTH: usize = 44;
+
+    fn column_lines(pane: &PaneState) -> Vec<String> {
+        pane.entries
+            .iter()
+            .filter(|e| e.name != "..")
+            .map(|e| {
+                let size = if e.is_dir {
+                    "<DIR>".to_string()
+                } else {
+                    platform::format_file_size(e.size)
+                };
+                format!("{}  {}", e.name, size)
+            })
+            .collect()
+    }
+
+    let left_lines = column_lines(left);
+    let right_lines = column_lines(right);
+
+    let left_path = left.current_path.display().to_string();
+    let left_count = format!("{} entries", left_lines.len());
+    let mut report = format!(
+        "Panel report, {}\n{:<width$} | {}\n{:<width$} | {} entries\n{}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        left_path,
+        right.current_path.display(),
+        left_count,
+        right_lines.len(),
+        "-".repeat(COLUMN_WIDTH * 2 + 3),
+        width = COLUMN_WIDTH,
+    );
+
+    for i in 0..left_lines.len().max(right_lines.len()) {
+        let left_cell = left_lines.get(i).map(String::as_str).unwrap_or("");
+        let right_cell = right_lines.get(i).map(String::as_str).unwrap_or("");
+        report.push_str(&format!(
+            "{:<width$.width$} | {:.width$}\n",
+            left_cell,
+            right_cell,
+            width = COLUMN_WIDTH,
+        ));
+    }
+
+    report
+}
+
+/// Expand the pane variables understood by the command prompt: `%l`/`%r`
+/// are the left/right pane paths, `%L`/`%R` their selected entry names (or
+/// the cursor entry when nothing is selected), `%f` the cursor entry in the
+/// active pane, and `%%` a literal percent sign. Values with whitespace are
+/// single-quoted so they survive the shell.
+pub fn expand_command_variables(
+    template: &str,
+    left: &PaneState,
+    right: &PaneState,
+    active_pane: usize,
+) -> String {
+    fn quoted(value: &str) -> String {
+        if value.chars().any(char::is_whitespace) {
+            format!("'{}'", value.replace('\'', "'\\''"))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn selection_names(pane: &PaneState) -> String {
+        // Listing order, not HashSet order, so the command is reproducible
+        let mut names: Vec<String> = pane
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| pane.selected_indices.contains(i))
+            .map(|(_, e)| quoted(&e.name))
+            .collect();
+        if names.is_empty() {
+            if let Some(entry) = pane.get_current_entry().filter(|e| e.name != "..") {
+                names.push(quoted(&entry.name));
+            }
+        }
+        names.join(" ")
+    }
+
+    let active = if active_pane == 0 { left } else { right };
+    let mut expanded = String::with_capacity(template.len());
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('l') => expanded.push_str(&quoted(&left.current_path.to_string_lossy())),
+            Some('r') => expanded.push_str(&quoted(&right.current_path.to_string_lossy())),
+            Some('L') => expanded.push_str(&selection_names(left)),
+            Some('R') => expanded.push_str(&selection_names(right)),
+            Some('f') => {
+                if let Some(entry) = active.get_current_entry().filter(|e| e.name != "..") {
+                    expanded.push_str(&quoted(&entry.name));
+                }
+            },
+            Some('%') => expanded.push('%'),
+            // Unknown sequences pass through untouched, like printf's do
+            Some(other) => {
+                expanded.push('%');
+                expanded.push(other);
+            },
+            None => expanded.push('%'),
+        }
+    }
+    expanded
+}
+
+/// Build a directory summary on a background thread so F3 on a large tree
+/// doesn't freeze the UI; the finished text arrives through the channel
+pub fn spawn_directory_summary(path: PathBuf) -> std::sync::mpsc::Receiver<String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let _ = tx.send(build_directory_summary(&path));
+    });
+
+    rx
+}
+
+fn scan_path_size(
+    path: &Path,
+    total: &mut u64,
+    files_seen: &mut u64,
+    tx: &std::sync::mpsc::Sender<(u64, u64)>,
+) -> Result<()> {
+    // Symlinks count as single entries; following them could loop forever
+    if is_symlink(path) {
+        *files_seen += 1;
+    } else if path.is_file() {
+        *total += fs::metadata(path)?.len();
+        *files_seen += 1;
+        // Stream an update every so often so the UI can show progress
+        if *files_seen % 256 == 0 {
+            let _ = tx.send((*files_seen, *total));
+        }
+    } else if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            scan_path_size(&entry.path(), total, files_seen, tx)?;
+        }
+    }
+    Ok(())
 }
 
 fn get_path_size(path: &Path) -> Result<u64> {
@@ -451,6 +1608,131 @@ fn get_path_size(path: &Path) -> Result<u64> {
     }
 }
 
+/// Recursively count files and sum their sizes under a set of paths.
+/// Used for the stronger delete confirmation on non-empty directories.
+pub fn directory_stats(paths: &[PathBuf]) -> Result<(u64, u64)> {
+    let mut file_count = 0;
+    let mut total_size = 0;
+
+    for path in paths {
+        collect_directory_stats(path, &mut file_count, &mut total_size)?;
+    }
+
+    Ok((file_count, total_size))
+}
+
+fn collect_directory_stats(path: &Path, file_count: &mut u64, total_size: &mut u64) -> Result<()> {
+    if path.is_file() {
+        *file_count += 1;
+        *total_size += fs::metadata(path)?.len();
+    } else if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            collect_directory_stats(&entry.path(), file_count, total_size)?;
+        }
+    }
+    Ok(())
+}
+
+/// Find other paths under `scan_root` that are hard links to `target`,
+/// matching on device and inode. Returns at most `limit` results and never
+/// includes `target` itself.
+pub fn find_hardlinks(scan_root: &Path, target: &Path, limit: usize) -> Result<Vec<PathBuf>> {
+    let target_id = platform::get_file_id(&fs::metadata(target)?);
+    let mut found = Vec::new();
+
+    if target_id.is_some() {
+        collect_hardlinks(scan_root, target, target_id, limit, &mut found)?;
+    }
+
+    Ok(found)
+}
+
+fn collect_hardlinks(
+    dir: &Path,
+    target: &Path,
+    target_id: Option<(u64, u64)>,
+    limit: usize,
+    found: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        if found.len() >= limit {
+            return Ok(());
+        }
+
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            let _ = collect_hardlinks(&path, target, target_id, limit, found);
+        } else if path != target && platform::get_file_id(&metadata) == target_id {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Check whether a directory contains any entries
+pub fn is_directory_empty(path: &Path) -> bool {
+    fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(true)
+}
+
+/// Resolve a configured or restored start directory that may no longer exist
+/// (unplugged drive, deleted folder). Walks up to the nearest existing
+/// ancestor, then the home directory, so startup never fails on a stale path.
+/// Returns the usable path and a notice describing the fallback, if any.
+pub fn resolve_start_path(path: PathBuf) -> (PathBuf, Option<String>) {
+    if path.is_dir() {
+        return (path, None);
+    }
+
+    let mut fallback = path.clone();
+    while !fallback.is_dir() {
+        match fallback.parent() {
+            Some(parent) => fallback = parent.to_path_buf(),
+            None => {
+                fallback = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+                break;
+            }
+        }
+    }
+
+    let notice = format!(
+        "Start directory '{}' is not available; opened '{}' instead",
+        path.display(),
+        fallback.display()
+    );
+    (fallback, Some(notice))
+}
+
+/// Check whether an entry name matches any of the exclude patterns.
+/// Patterns ending in `/` (e.g. `target/`, `.git/`) only match directories;
+/// everything else is matched against the plain entry name as a glob.
+fn is_excluded(name: &str, is_dir: bool, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        if let Some(dir_pattern) = pattern.strip_suffix('/') {
+            is_dir && matches_glob_pattern(name, dir_pattern)
+        } else {
+            matches_glob_pattern(name, pattern)
+        }
+    })
+}
+
+/// Glob matching honoring the configured case sensitivity mode
+fn matches_glob_pattern_cased(name: &str, pattern: &str, case: CaseSensitivity) -> bool {
+    if case.is_sensitive(pattern) {
+        matches_glob_pattern(name, pattern)
+    } else {
+        matches_glob_pattern(&name.to_lowercase(), &pattern.to_lowercase())
+    }
+}
+
 fn matches_glob_pattern(name: &str, pattern: &str) -> bool {
     // Simple glob pattern matching
     if pattern == "*" {
@@ -465,151 +1747,803 @@ fn matches_glob_pattern(name: &str, pattern: &str) -> bool {
             return name.starts_with(prefix) && name.ends_with(suffix);
         }
     }
-    
-    name == pattern
-}
+    
+    name == pattern
+}
+
+pub fn is_supported_archive(path: &Path) -> bool {
+    if let Some(extension) = path.extension() {
+        if let Some(ext_str) = extension.to_str() {
+            match ext_str.to_lowercase().as_str() {
+                "zip" | "tar" | "gz" | "tgz" => return true,
+                _ => {}
+            }
+        }
+    }
+    
+    // Check for .tar.gz, .tar.bz2, etc.
+    if let Some(name) = path.file_name() {
+        if let Some(name_str) = name.to_str() {
+            let name_lower = name_str.to_lowercase();
+            return name_lower.ends_with(".tar.gz") || 
+                   name_lower.ends_with(".tar.bz2") ||
+                   name_lower.ends_with(".tar.xz");
+        }
+    }
+    
+    false
+}
+
+pub fn create_directory(path: &Path, name: &str) -> Result<PathBuf> {
+    create_directory_with_mode(path, name, None)
+}
+
+/// Create the directories named in F7 input: `;`-separated names, each of
+/// which may be a nested path like `a/b/c` whose intermediate directories
+/// are created as needed. Returns the innermost directory of each name.
+pub fn create_directories(path: &Path, input: &str, mode: Option<u32>) -> Result<Vec<PathBuf>> {
+    let mut created = Vec::new();
+    for name in input.split(';').map(str::trim).filter(|name| !name.is_empty()) {
+        created.push(create_directory_with_mode(path, name, mode)?);
+    }
+    Ok(created)
+}
+
+/// Create a directory, optionally forcing an explicit mode (from `NewDirMode`).
+/// Without an explicit mode the process umask applies as usual. Nested names
+/// create their intermediate directories, with the mode applied to each one.
+pub fn create_directory_with_mode(path: &Path, name: &str, mode: Option<u32>) -> Result<PathBuf> {
+    let new_dir = path.join(name);
+
+    if new_dir.exists() {
+        return Err(GeekCommanderError::FileOperation(format!("Directory '{}' already exists", name)));
+    }
+
+    // Remember which components are new, so an explicit mode reaches the
+    // intermediate directories too
+    let mut to_create = vec![new_dir.clone()];
+    while let Some(parent) = to_create.last().and_then(|p| p.parent()) {
+        if parent.as_os_str().is_empty() || parent.exists() {
+            break;
+        }
+        to_create.push(parent.to_path_buf());
+    }
+
+    fs::create_dir_all(&new_dir)?;
+
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        for dir in &to_create {
+            fs::set_permissions(dir, fs::Permissions::from_mode(mode))?;
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    Ok(new_dir)
+}
+
+/// Rename `old_path` to `new_name`, which may be a bare name, a relative
+/// path (resolved against the old location), or an absolute path. Missing
+/// intermediate directories are created, so F6 can relocate as well as
+/// rename, matching classic commander behavior.
+pub fn rename_file(old_path: &Path, new_name: &str) -> Result<PathBuf> {
+    let parent = old_path.parent()
+        .ok_or_else(|| GeekCommanderError::FileOperation("Cannot determine parent directory".to_string()))?;
+
+    // join() keeps an absolute new_name as-is and resolves a relative one
+    // against the old parent
+    let new_path = parent.join(new_name);
+
+    if new_path.exists() {
+        return Err(GeekCommanderError::FileOperation(format!("File '{}' already exists", new_name)));
+    }
+
+    if let Some(new_parent) = new_path.parent() {
+        fs::create_dir_all(new_parent)?;
+    }
+
+    fs::rename(old_path, &new_path)?;
+    Ok(new_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_matches_glob_pattern() {
+        assert!(matches_glob_pattern("test.txt", "*.txt"));
+        assert!(matches_glob_pattern("file.log", "*.log"));
+        assert!(matches_glob_pattern("anything", "*"));
+        assert!(!matches_glob_pattern("test.txt", "*.log"));
+        assert!(matches_glob_pattern("exact_match", "exact_match"));
+    }
+
+    #[test]
+    fn test_is_supported_archive() {
+        assert!(is_supported_archive(Path::new("test.zip")));
+        assert!(is_supported_archive(Path::new("test.tar")));
+        assert!(is_supported_archive(Path::new("test.tar.gz")));
+        assert!(is_supported_archive(Path::new("test.tgz")));
+        assert!(!is_supported_archive(Path::new("test.txt")));
+        assert!(!is_supported_archive(Path::new("test")));
+    }
+
+    #[test]
+    fn test_pane_state_creation() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        
+        assert_eq!(pane.current_path, temp_dir.path());
+        assert_eq!(pane.cursor_index, 0);
+        assert_eq!(pane.scroll_offset, 0);
+        assert!(pane.selected_indices.is_empty());
+        assert!(pane.archive_context.is_none());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_named() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        File::create(temp_dir.path().join("a.txt")).unwrap();
+        File::create(temp_dir.path().join("b.txt")).unwrap();
+        File::create(temp_dir.path().join("c.txt")).unwrap();
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+
+        // Bare names and full paths both match; unknown names are ignored
+        let names = vec![
+            "a.txt".to_string(),
+            temp_dir.path().join("c.txt").to_string_lossy().to_string(),
+            "missing.txt".to_string(),
+        ];
+        assert_eq!(pane.select_named(&names), 2);
+
+        let selected: Vec<String> = pane.get_selected_entries().iter().map(|e| e.name.clone()).collect();
+        assert!(selected.contains(&"a.txt".to_string()));
+        assert!(selected.contains(&"c.txt".to_string()));
+        assert!(!selected.contains(&"b.txt".to_string()));
+
+        // Selecting the same names again adds nothing new
+        assert_eq!(pane.select_named(&names), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_previous_directory() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("sub");
+        std::fs::create_dir(&sub)?;
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+
+        // No previous directory yet: the toggle is a no-op
+        pane.toggle_previous_directory()?;
+        assert_eq!(pane.current_path, temp_dir.path());
+
+        pane.enter_directory(sub.clone())?;
+        assert_eq!(pane.current_path, sub);
+
+        // Toggling flips back and forth between the two directories
+        pane.toggle_previous_directory()?;
+        assert_eq!(pane.current_path, temp_dir.path());
+        pane.toggle_previous_directory()?;
+        assert_eq!(pane.current_path, sub);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_selection() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        
+        // Create a test file
+        let test_file = temp_dir.path().join("test.txt");
+        File::create(&test_file).unwrap();
+        
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        
+        // Should have at least the test file
+        assert!(!pane.entries.is_empty());
+        
+        // Toggle selection on first entry
+        pane.cursor_index = 0;
+        pane.toggle_selection();
+        assert!(pane.selected_indices.contains(&0));
+        
+        // Toggle again to deselect
+        pane.toggle_selection();
+        assert!(!pane.selected_indices.contains(&0));
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_by_pattern() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        
+        // Create test files
+        File::create(temp_dir.path().join("test1.txt")).unwrap();
+        File::create(temp_dir.path().join("test2.txt")).unwrap();
+        File::create(temp_dir.path().join("other.log")).unwrap();
+        
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        
+        let count = pane.select_by_pattern("*.txt")?;
+        assert_eq!(count, 2); // Should select the two .txt files
+        assert_eq!(pane.selected_indices.len(), 2);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_path_size() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.txt");
+        
+        // Create a file with known content
+        std::fs::write(&test_file, "Hello, world!").unwrap();
+        
+        let size = get_path_size(&test_file)?;
+        assert_eq!(size, 13); // "Hello, world!" is 13 bytes
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_total_size_background() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "12345")?;
+        std::fs::write(temp_dir.path().join("b.txt"), "1234567890")?;
+
+        let rx = scan_total_size_background(vec![temp_dir.path().to_path_buf()]);
+
+        // The final totals are the last values sent before the channel closes
+        let mut last = (0, 0);
+        while let Ok(totals) = rx.recv() {
+            last = totals;
+        }
+        assert_eq!(last, (2, 15));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn_dir_size_scan() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let sub_a = temp_dir.path().join("a");
+        let sub_b = temp_dir.path().join("b");
+        std::fs::create_dir(&sub_a)?;
+        std::fs::create_dir(&sub_b)?;
+        std::fs::write(sub_a.join("file.txt"), "12345")?;
+        std::fs::write(sub_b.join("nested.txt"), "1234567890")?;
+
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let rx = spawn_dir_size_scan(vec![sub_a.clone(), sub_b.clone()], cancel);
+
+        let sizes: std::collections::HashMap<_, _> = rx.iter().collect();
+        assert_eq!(sizes.get(&sub_a), Some(&5));
+        assert_eq!(sizes.get(&sub_b), Some(&10));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn_dir_count_scan() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let full = temp_dir.path().join("full");
+        let empty = temp_dir.path().join("empty");
+        std::fs::create_dir(&full)?;
+        std::fs::create_dir(&empty)?;
+        std::fs::write(full.join("one.txt"), "1")?;
+        std::fs::create_dir(full.join("two"))?;
+        // Only immediate children are counted, not grandchildren
+        std::fs::write(full.join("two").join("nested.txt"), "x")?;
+
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let rx = spawn_dir_count_scan(vec![full.clone(), empty.clone()], cancel);
+
+        let counts: std::collections::HashMap<_, _> = rx.iter().collect();
+        assert_eq!(counts.get(&full), Some(&2));
+        assert_eq!(counts.get(&empty), Some(&0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_operation_state_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let state_file = temp_dir.path().join("pending-operation.toml");
+
+        let operation = copy_paths(
+            vec![temp_dir.path().join("a.txt"), temp_dir.path().join("b.txt")],
+            &temp_dir.path().join("dest"),
+        );
+        save_operation_state(&state_file, &operation)?;
+
+        let loaded = load_operation_state(&state_file).expect("state should load back");
+        assert_eq!(loaded, operation);
+
+        clear_operation_state(&state_file);
+        assert!(load_operation_state(&state_file).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_refresh_if_changed_detects_dir_mtime() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a")?;
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        assert!(!pane.refresh_if_changed()?, "unchanged directory must not re-read");
+
+        // Creating an entry bumps the directory's mtime
+        std::fs::write(temp_dir.path().join("b.txt"), "b")?;
+        assert!(pane.refresh_if_changed()?);
+        assert!(pane.entries.iter().any(|e| e.name == "b.txt"));
+        assert!(!pane.refresh_if_changed()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flat_view_lists_files_recursively() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("top.txt"), "t")?;
+        std::fs::create_dir(temp_dir.path().join("sub"))?;
+        std::fs::write(temp_dir.path().join("sub").join("nested.txt"), "n")?;
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        pane.flat_view = true;
+        pane.refresh()?;
+
+        let nested_name = format!("sub{}nested.txt", std::path::MAIN_SEPARATOR);
+        let nested = pane
+            .entries
+            .iter()
+            .find(|e| e.name == nested_name)
+            .expect("nested file shown by relative path");
+        // The entry points at the real file, so operations act on it
+        assert_eq!(nested.path, temp_dir.path().join("sub").join("nested.txt"));
+        assert!(pane.entries.iter().any(|e| e.name == "top.txt"));
+        // Directories themselves are not listed in flat view
+        assert!(!pane.entries.iter().any(|e| e.name == "sub"));
+
+        pane.flat_view = false;
+        pane.refresh()?;
+        assert!(pane.entries.iter().any(|e| e.name == "sub" && e.is_dir));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_git_changed_files() -> Result<()> {
+        // Without git on the PATH there is nothing to test here
+        if std::process::Command::new("git").arg("--version").output().is_err() {
+            return Ok(());
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .expect("git runs")
+        };
+
+        assert!(git(&["init", "-q"]).status.success());
+        std::fs::write(root.join("tracked.txt"), "v1")?;
+        std::fs::create_dir(root.join("sub"))?;
+        std::fs::write(root.join("sub").join("nested.txt"), "v1")?;
+        assert!(git(&["add", "."]).status.success());
+        assert!(git(&["commit", "-q", "-m", "initial"]).status.success());
+
+        std::fs::write(root.join("tracked.txt"), "v2")?;
+        std::fs::write(root.join("sub").join("nested.txt"), "v2")?;
+        std::fs::write(root.join("fresh.txt"), "new")?;
+
+        let changed = git_changed_files(root, "HEAD")?;
+        assert!(changed.contains(&"tracked.txt".to_string()));
+        assert!(changed.contains(&"sub/nested.txt".to_string()));
+        // Untracked files count as changed
+        assert!(changed.contains(&"fresh.txt".to_string()));
+
+        // A bad ref surfaces git's complaint instead of an empty list
+        assert!(git_changed_files(root, "no-such-ref").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_panel_report_side_by_side() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let left_dir = temp_dir.path().join("left");
+        let right_dir = temp_dir.path().join("right");
+        std::fs::create_dir(&left_dir)?;
+        std::fs::create_dir(&right_dir)?;
+        std::fs::write(left_dir.join("alpha.txt"), "12345")?;
+        std::fs::write(left_dir.join("beta.txt"), "1")?;
+        std::fs::write(right_dir.join("gamma.txt"), "12")?;
+
+        let left = PaneState::new(left_dir)?;
+        let right = PaneState::new(right_dir)?;
+        let report = build_panel_report(&left, &right);
+
+        assert!(report.contains("2 entries"));
+        assert!(report.contains("1 entries"));
+        // Both columns on shared lines, the shorter side padded with blanks
+        let first_row = report.lines().nth(4).expect("first entry row");
+        assert!(first_row.contains("alpha.txt") && first_row.contains("gamma.txt"));
+        let second_row = report.lines().nth(5).expect("second entry row");
+        assert!(second_row.contains("beta.txt") && second_row.trim_end().ends_with('|'));
+        // The parent pseudo-entry is not part of the report
+        assert!(!report.contains(".."));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_command_variables() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let left_dir = temp_dir.path().join("left");
+        let right_dir = temp_dir.path().join("right");
+        std::fs::create_dir(&left_dir)?;
+        std::fs::create_dir(&right_dir)?;
+        std::fs::write(left_dir.join("notes.txt"), "x")?;
+        std::fs::write(right_dir.join("a.txt"), "x")?;
+        std::fs::write(right_dir.join("spaced name.txt"), "x")?;
+
+        let mut left = PaneState::new(left_dir.clone())?;
+        let mut right = PaneState::new(right_dir.clone())?;
+        left.focus_entry("notes.txt");
+        right.select_named(&["a.txt".to_string(), "spaced name.txt".to_string()]);
+
+        let expanded = expand_command_variables("diff %f %R in %l vs %r", &left, &right, 0);
+        assert_eq!(
+            expanded,
+            format!(
+                "diff notes.txt a.txt 'spaced name.txt' in {} vs {}",
+                left_dir.display(),
+                right_dir.display()
+            )
+        );
+
+        // Without a selection, %L falls back to the cursor entry
+        left.focus_entry("notes.txt");
+        assert_eq!(expand_command_variables("cat %L", &left, &right, 0), "cat notes.txt");
+        // Literal percent and unknown sequences pass through
+        assert_eq!(expand_command_variables("date +%%s %q", &left, &right, 0), "date +%s %q");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recent_files_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("recent-files.txt");
+
+        let kept = temp_dir.path().join("kept.txt");
+        let gone = temp_dir.path().join("gone.txt");
+        std::fs::write(&kept, "x")?;
+
+        // A deleted file is dropped on load instead of shown in the overlay
+        save_recent_files(&history_file, &[kept.clone(), gone])?;
+        assert_eq!(load_recent_files(&history_file), vec![kept]);
+
+        // A missing history file is just an empty list
+        assert!(load_recent_files(&temp_dir.path().join("absent.txt")).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resume_skips_completed_files() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src");
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&source_dir)?;
+        std::fs::create_dir(&dest_dir)?;
+
+        std::fs::write(source_dir.join("done.txt"), "AAAA")?;
+        std::fs::write(source_dir.join("todo.txt"), "BBBB")?;
+        // Same size as the source: counts as already copied and must be
+        // left alone, different content proving it was not rewritten
+        std::fs::write(dest_dir.join("done.txt"), "XXXX")?;
+
+        let mut operation = copy_paths(
+            vec![source_dir.join("done.txt"), source_dir.join("todo.txt")],
+            &dest_dir,
+        );
+        operation.resume = true;
+        execute_operation(&mut operation)?;
+
+        assert_eq!(std::fs::read_to_string(dest_dir.join("done.txt"))?, "XXXX");
+        assert_eq!(std::fs::read_to_string(dest_dir.join("todo.txt"))?, "BBBB");
+        assert_eq!(operation.files_completed, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_directory_summary() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("docs");
+        std::fs::create_dir(&sub)?;
+        std::fs::write(temp_dir.path().join("a.txt"), "12345")?;
+        std::fs::write(sub.join("b.txt"), "123")?;
+        std::fs::write(sub.join("notes.md"), "1234567890")?;
+
+        let summary = build_directory_summary(temp_dir.path());
+
+        assert!(summary.contains("1 directories, 3 files"));
+        // txt is the most common extension and is listed with its totals
+        assert!(summary.contains("txt"));
+        assert!(summary.contains("2 file(s)"));
+        assert!(summary.contains("md"));
+        // The tree section lists the nested entries indented under docs/
+        assert!(summary.contains("docs/"));
+        assert!(summary.contains("  b.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("plain"), "plain");
+        assert_eq!(json_escape("a\"b\\c"), "a\\\"b\\\\c");
+        assert_eq!(json_escape("tab\there"), "tab\\there");
+        assert_eq!(json_escape("bell\x07"), "bell\\u0007");
+    }
 
-pub fn is_supported_archive(path: &Path) -> bool {
-    if let Some(extension) = path.extension() {
-        if let Some(ext_str) = extension.to_str() {
-            match ext_str.to_lowercase().as_str() {
-                "zip" | "tar" | "gz" | "tgz" => return true,
-                _ => {}
-            }
-        }
+    #[test]
+    fn test_audit_log_records_operations() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let audit_file = temp_dir.path().join("audit.jsonl");
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest_dir)?;
+
+        let source = temp_dir.path().join("file.txt");
+        std::fs::write(&source, "12345")?;
+
+        let mut operation = copy_paths(vec![source.clone()], &dest_dir);
+        operation.audit_file = Some(audit_file.clone());
+        execute_operation(&mut operation)?;
+
+        let mut operation = copy_paths(vec![source.clone()], Path::new(""));
+        operation.operation_type = OperationType::Delete;
+        operation.audit_file = Some(audit_file.clone());
+        execute_operation(&mut operation)?;
+
+        let log = std::fs::read_to_string(&audit_file)?;
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"action\":\"copy\""));
+        assert!(lines[0].contains("\"size\":5"));
+        assert!(lines[0].contains("\"result\":\"ok\""));
+        assert!(lines[0].contains(&format!("\"source\":\"{}\"", json_escape(&source.to_string_lossy()))));
+        assert!(lines[1].contains("\"action\":\"delete\""));
+        assert!(lines[1].contains("\"result\":\"ok\""));
+
+        Ok(())
     }
-    
-    // Check for .tar.gz, .tar.bz2, etc.
-    if let Some(name) = path.file_name() {
-        if let Some(name_str) = name.to_str() {
-            let name_lower = name_str.to_lowercase();
-            return name_lower.ends_with(".tar.gz") || 
-                   name_lower.ends_with(".tar.bz2") ||
-                   name_lower.ends_with(".tar.xz");
-        }
+
+    #[test]
+    fn test_is_excluded() {
+        let patterns = vec!["*.o".to_string(), "target/".to_string(), ".git/".to_string()];
+        assert!(is_excluded("main.o", false, &patterns));
+        assert!(is_excluded("target", true, &patterns));
+        assert!(is_excluded(".git", true, &patterns));
+        assert!(!is_excluded("target", false, &patterns)); // a plain file named "target"
+        assert!(!is_excluded("main.rs", false, &patterns));
+        assert!(!is_excluded("src", true, &patterns));
     }
-    
-    false
-}
 
-pub fn create_directory(path: &Path, name: &str) -> Result<PathBuf> {
-    let new_dir = path.join(name);
-    
-    if new_dir.exists() {
-        return Err(GeekCommanderError::FileOperation(format!("Directory '{}' already exists", name)));
+    #[test]
+    fn test_copy_with_excludes() -> Result<()> {
+        let src_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+
+        let tree = src_dir.path().join("project");
+        std::fs::create_dir(&tree)?;
+        std::fs::create_dir(tree.join("target"))?;
+        std::fs::write(tree.join("main.rs"), "fn main() {}")?;
+        std::fs::write(tree.join("main.o"), "object code")?;
+        std::fs::write(tree.join("target").join("artifact"), "big")?;
+
+        let pane = PaneState::new(src_dir.path().to_path_buf())?;
+        let entry = pane.entries.iter().find(|e| e.name == "project").unwrap();
+
+        let mut operation = copy_files_with_excludes(
+            &[entry],
+            dest_dir.path(),
+            vec!["*.o".to_string(), "target/".to_string()],
+        )?;
+        execute_operation(&mut operation)?;
+
+        let copied = dest_dir.path().join("project");
+        assert!(copied.join("main.rs").exists());
+        assert!(!copied.join("main.o").exists());
+        assert!(!copied.join("target").exists());
+
+        Ok(())
     }
-    
-    fs::create_dir(&new_dir)?;
-    Ok(new_dir)
-}
 
-pub fn rename_file(old_path: &Path, new_name: &str) -> Result<PathBuf> {
-    let parent = old_path.parent()
-        .ok_or_else(|| GeekCommanderError::FileOperation("Cannot determine parent directory".to_string()))?;
-    
-    let new_path = parent.join(new_name);
-    
-    if new_path.exists() {
-        return Err(GeekCommanderError::FileOperation(format!("File '{}' already exists", new_name)));
+    #[test]
+    fn test_move_keeps_unreliable_and_excluded_sources() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&src)?;
+        std::fs::create_dir(&dest)?;
+        let flagged = src.join("flagged.txt");
+        let moved = src.join("moved.txt");
+        let skipped = src.join("skipped.log");
+        std::fs::write(&flagged, "only faithful copy")?;
+        std::fs::write(&moved, "x")?;
+        std::fs::write(&skipped, "x")?;
+
+        let mut operation = move_paths(
+            vec![flagged.clone(), moved.clone(), skipped.clone()],
+            &dest,
+        );
+        operation.exclude_patterns = vec!["*.log".to_string()];
+        // Simulate a source that changed while its copy was running
+        operation.unreliable_sources = vec![flagged.clone()];
+        execute_operation(&mut operation)?;
+
+        // The unreliable source survives the delete phase, with a warning
+        assert!(flagged.exists());
+        assert!(operation.warnings.iter().any(|w| w.contains("flagged.txt")));
+        // The clean source moved normally
+        assert!(!moved.exists());
+        assert!(dest.join("moved.txt").exists());
+        // The excluded file was neither copied nor deleted
+        assert!(skipped.exists());
+        assert!(!dest.join("skipped.log").exists());
+
+        Ok(())
     }
-    
-    fs::rename(old_path, &new_path)?;
-    Ok(new_path)
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::File;
-    use tempfile::TempDir;
+    #[test]
+    fn test_copy_preserves_sparse_content() -> Result<()> {
+        use std::io::SeekFrom;
+
+        let src_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+
+        // A 1 MB file that is almost entirely a hole, with a marker at the end
+        let source = src_dir.path().join("sparse.img");
+        let mut file = File::create(&source)?;
+        file.set_len(1024 * 1024)?;
+        file.seek(SeekFrom::End(-4))?;
+        file.write_all(b"tail")?;
+        drop(file);
+
+        let mut operation = copy_paths(vec![source], dest_dir.path());
+        execute_operation(&mut operation)?;
+
+        let copied = dest_dir.path().join("sparse.img");
+        let content = std::fs::read(&copied)?;
+        assert_eq!(content.len(), 1024 * 1024);
+        assert_eq!(&content[content.len() - 4..], b"tail");
+        assert!(content[..content.len() - 4].iter().all(|&b| b == 0));
+
+        Ok(())
+    }
 
     #[test]
-    fn test_matches_glob_pattern() {
-        assert!(matches_glob_pattern("test.txt", "*.txt"));
-        assert!(matches_glob_pattern("file.log", "*.log"));
-        assert!(matches_glob_pattern("anything", "*"));
-        assert!(!matches_glob_pattern("test.txt", "*.log"));
-        assert!(matches_glob_pattern("exact_match", "exact_match"));
+    fn test_create_directories_nested_and_multiple() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+
+        let created = create_directories(temp_dir.path(), "a/b/c; docs ;", None)?;
+        assert_eq!(created.len(), 2);
+        assert!(temp_dir.path().join("a").join("b").join("c").is_dir());
+        assert!(temp_dir.path().join("docs").is_dir());
+
+        // An existing target still fails rather than being silently accepted
+        assert!(create_directories(temp_dir.path(), "docs", None).is_err());
+
+        Ok(())
     }
 
     #[test]
-    fn test_is_supported_archive() {
-        assert!(is_supported_archive(Path::new("test.zip")));
-        assert!(is_supported_archive(Path::new("test.tar")));
-        assert!(is_supported_archive(Path::new("test.tar.gz")));
-        assert!(is_supported_archive(Path::new("test.tgz")));
-        assert!(!is_supported_archive(Path::new("test.txt")));
-        assert!(!is_supported_archive(Path::new("test")));
+    fn test_directory_stats() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let sub_dir = temp_dir.path().join("sub");
+        std::fs::create_dir(&sub_dir)?;
+        std::fs::write(temp_dir.path().join("a.txt"), "12345")?;
+        std::fs::write(sub_dir.join("b.txt"), "1234567890")?;
+
+        let (count, size) = directory_stats(&[temp_dir.path().to_path_buf()])?;
+        assert_eq!(count, 2);
+        assert_eq!(size, 15);
+
+        Ok(())
     }
 
     #[test]
-    fn test_pane_state_creation() -> Result<()> {
+    #[cfg(unix)]
+    fn test_find_hardlinks() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
-        let pane = PaneState::new(temp_dir.path().to_path_buf())?;
-        
-        assert_eq!(pane.current_path, temp_dir.path());
-        assert_eq!(pane.cursor_index, 0);
-        assert_eq!(pane.scroll_offset, 0);
-        assert!(pane.selected_indices.is_empty());
-        assert!(pane.archive_context.is_none());
-        
+        let original = temp_dir.path().join("original.txt");
+        std::fs::write(&original, "content")?;
+
+        let sub_dir = temp_dir.path().join("sub");
+        std::fs::create_dir(&sub_dir)?;
+        let link = sub_dir.join("link.txt");
+        std::fs::hard_link(&original, &link)?;
+
+        let found = find_hardlinks(temp_dir.path(), &original, 10)?;
+        assert_eq!(found, vec![link]);
+
+        // A file with a single link has no other hardlinks
+        let lonely = temp_dir.path().join("lonely.txt");
+        std::fs::write(&lonely, "alone")?;
+        assert!(find_hardlinks(temp_dir.path(), &lonely, 10)?.is_empty());
+
         Ok(())
     }
 
     #[test]
-    fn test_toggle_selection() -> Result<()> {
+    fn test_is_directory_empty() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
-        
-        // Create a test file
-        let test_file = temp_dir.path().join("test.txt");
-        File::create(&test_file).unwrap();
-        
-        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
-        
-        // Should have at least the test file
-        assert!(!pane.entries.is_empty());
-        
-        // Toggle selection on first entry
-        pane.cursor_index = 0;
-        pane.toggle_selection();
-        assert!(pane.selected_indices.contains(&0));
-        
-        // Toggle again to deselect
-        pane.toggle_selection();
-        assert!(!pane.selected_indices.contains(&0));
-        
+        assert!(is_directory_empty(temp_dir.path()));
+
+        File::create(temp_dir.path().join("file.txt")).unwrap();
+        assert!(!is_directory_empty(temp_dir.path()));
+
         Ok(())
     }
 
     #[test]
-    fn test_select_by_pattern() -> Result<()> {
+    #[cfg(unix)]
+    fn test_non_utf8_filename_keeps_raw_path() -> Result<()> {
+        use std::os::unix::ffi::OsStringExt;
+
         let temp_dir = TempDir::new().unwrap();
-        
-        // Create test files
-        File::create(temp_dir.path().join("test1.txt")).unwrap();
-        File::create(temp_dir.path().join("test2.txt")).unwrap();
-        File::create(temp_dir.path().join("other.log")).unwrap();
-        
-        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
-        
-        let count = pane.select_by_pattern("*.txt")?;
-        assert_eq!(count, 2); // Should select the two .txt files
-        assert_eq!(pane.selected_indices.len(), 2);
-        
+        let raw_name = std::ffi::OsString::from_vec(vec![b'f', 0xff, b'o']);
+        let raw_path = temp_dir.path().join(&raw_name);
+        File::create(&raw_path)?;
+
+        let pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        let entry = pane.entries.iter().find(|e| e.name != "..").unwrap();
+
+        // The display name is lossy, but the raw name and path are intact
+        assert!(entry.name.contains('\u{FFFD}'));
+        assert_eq!(entry.os_name, raw_name);
+        assert_eq!(entry.path, raw_path);
+        assert!(entry.path.exists());
+
         Ok(())
     }
 
     #[test]
-    fn test_get_path_size() -> Result<()> {
+    fn test_resolve_start_path() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
-        let test_file = temp_dir.path().join("test.txt");
-        
-        // Create a file with known content
-        std::fs::write(&test_file, "Hello, world!").unwrap();
-        
-        let size = get_path_size(&test_file)?;
-        assert_eq!(size, 13); // "Hello, world!" is 13 bytes
-        
+
+        // An existing directory is returned as-is, with no notice
+        let (path, notice) = resolve_start_path(temp_dir.path().to_path_buf());
+        assert_eq!(path, temp_dir.path());
+        assert!(notice.is_none());
+
+        // A missing directory falls back to the nearest existing ancestor
+        let missing = temp_dir.path().join("gone").join("deeper");
+        let (path, notice) = resolve_start_path(missing);
+        assert_eq!(path, temp_dir.path());
+        assert!(notice.is_some());
+
         Ok(())
     }
 
@@ -628,6 +2562,102 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_create_directory_with_mode() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let new_dir = create_directory_with_mode(temp_dir.path(), "restricted", Some(0o750))?;
+
+        let mode = std::fs::metadata(&new_dir)?.permissions().mode() & 0o777;
+        assert_eq!(mode, 0o750);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_copy_preserves_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+
+        let script = src_dir.path().join("run.sh");
+        std::fs::write(&script, "#!/bin/sh\n")?;
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+        let pane = PaneState::new(src_dir.path().to_path_buf())?;
+        let entry = pane.entries.iter().find(|e| e.name == "run.sh").unwrap();
+
+        let mut operation = copy_files(&[entry], dest_dir.path())?;
+        execute_operation(&mut operation)?;
+
+        let copied_mode = std::fs::metadata(dest_dir.path().join("run.sh"))?.permissions().mode() & 0o777;
+        assert_eq!(copied_mode, 0o755);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_copy_into_self() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("sub");
+        std::fs::create_dir(&sub)?;
+
+        let root = vec![temp_dir.path().to_path_buf()];
+
+        // A directory cannot go into itself or one of its subdirectories
+        assert!(check_copy_into_self(&root, temp_dir.path()).is_err());
+        assert!(check_copy_into_self(&root, &sub).is_err());
+
+        // Copying a subdirectory up into its parent is fine, as are files
+        assert!(check_copy_into_self(&[sub.clone()], temp_dir.path()).is_ok());
+        let file = temp_dir.path().join("a.txt");
+        std::fs::write(&file, "x")?;
+        assert!(check_copy_into_self(&[file], &sub).is_ok());
+
+        // The whole copy operation refuses to run
+        let mut operation = copy_paths(root, &sub);
+        assert!(execute_operation(&mut operation).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_copy_recreates_symlinks() -> Result<()> {
+        let src_dir = TempDir::new().unwrap();
+        let dest_dir = TempDir::new().unwrap();
+
+        std::fs::write(src_dir.path().join("data.txt"), "hello")?;
+        std::os::unix::fs::symlink("data.txt", src_dir.path().join("link.txt"))?;
+
+        let mut operation = copy_paths(
+            vec![src_dir.path().join("data.txt"), src_dir.path().join("link.txt")],
+            dest_dir.path(),
+        );
+        execute_operation(&mut operation)?;
+
+        // The link is recreated as a link, pointing at the same target
+        let copied_link = dest_dir.path().join("link.txt");
+        assert!(std::fs::symlink_metadata(&copied_link)?.file_type().is_symlink());
+        assert_eq!(std::fs::read_link(&copied_link)?, PathBuf::from("data.txt"));
+
+        // With dereferencing requested, the target contents are copied instead
+        let deref_dir = TempDir::new().unwrap();
+        let mut operation = copy_paths(vec![src_dir.path().join("link.txt")], deref_dir.path());
+        operation.dereference_symlinks = true;
+        execute_operation(&mut operation)?;
+
+        let copied = deref_dir.path().join("link.txt");
+        assert!(!std::fs::symlink_metadata(&copied)?.file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&copied)?, "hello");
+
+        Ok(())
+    }
+
     #[test]
     fn test_rename_file() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
@@ -639,7 +2669,29 @@ mod tests {
         assert!(!original_file.exists());
         assert!(new_path.exists());
         assert_eq!(new_path.file_name().unwrap(), "renamed.txt");
-        
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_file_with_path() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+
+        // A relative path relocates under the old parent, creating the
+        // intermediate directories on the way
+        let original = temp_dir.path().join("a.txt");
+        std::fs::write(&original, "data")?;
+        let new_path = rename_file(&original, "nested/dir/b.txt")?;
+        assert!(!original.exists());
+        assert_eq!(new_path, temp_dir.path().join("nested/dir/b.txt"));
+        assert_eq!(std::fs::read_to_string(&new_path)?, "data");
+
+        // An absolute path moves the file wherever it points
+        let target = temp_dir.path().join("elsewhere").join("c.txt");
+        let moved = rename_file(&new_path, &target.to_string_lossy())?;
+        assert_eq!(moved, target);
+        assert!(target.exists());
+
         Ok(())
     }
 
@@ -977,6 +3029,128 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_case_sensitivity_modes() {
+        // Sensitive keeps the exact-case behavior
+        assert!(!matches_glob_pattern_cased("README.md", "readme*", CaseSensitivity::Sensitive));
+        assert!(matches_glob_pattern_cased("README.md", "README*", CaseSensitivity::Sensitive));
+
+        // Insensitive ignores case in both name and pattern
+        assert!(matches_glob_pattern_cased("README.md", "readme*", CaseSensitivity::Insensitive));
+        assert!(matches_glob_pattern_cased("readme.md", "README*", CaseSensitivity::Insensitive));
+
+        // Smart is insensitive until the pattern contains an uppercase letter
+        assert!(matches_glob_pattern_cased("README.md", "readme*", CaseSensitivity::Smart));
+        assert!(!matches_glob_pattern_cased("readme.md", "README*", CaseSensitivity::Smart));
+    }
+
+    #[test]
+    fn test_refresh_marks_new_entries() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("old.txt"), "old")?;
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+        assert!(!pane.is_recent_entry("old.txt"));
+
+        // A file that appears between refreshes gets the highlight
+        std::fs::write(temp_dir.path().join("new.txt"), "new")?;
+        pane.refresh()?;
+        assert!(pane.is_recent_entry("new.txt"));
+        assert!(!pane.is_recent_entry("old.txt"));
+
+        // Navigating to another directory drops the highlights
+        let sub = temp_dir.path().join("sub");
+        std::fs::create_dir(&sub)?;
+        pane.refresh()?;
+        pane.current_path = sub;
+        pane.refresh()?;
+        assert!(!pane.is_recent_entry("new.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_memory_restores_position() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub"))?;
+        std::fs::write(temp_dir.path().join("a.txt"), "a")?;
+        std::fs::write(temp_dir.path().join("b.txt"), "b")?;
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+
+        // Put the cursor on b.txt, descend into the subdirectory, come back
+        let b_index = pane.entries.iter().position(|e| e.name == "b.txt").unwrap();
+        pane.cursor_index = b_index;
+        pane.enter_directory(temp_dir.path().join("sub"))?;
+        assert_eq!(pane.cursor_index, 0);
+        pane.enter_directory(temp_dir.path().to_path_buf())?;
+
+        assert_eq!(pane.cursor_index, b_index);
+        assert_eq!(pane.get_current_entry().unwrap().name, "b.txt");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dirs_placement_sorting() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+
+        std::fs::create_dir(temp_dir.path().join("bdir")).unwrap();
+        File::create(temp_dir.path().join("afile.txt")).unwrap();
+        File::create(temp_dir.path().join("zfile.txt")).unwrap();
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+
+        // Default: directories first
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "bdir", "afile.txt", "zfile.txt"]);
+
+        // Mixed: pure alphabetical after ".."
+        pane.dirs_placement = DirsPlacement::Mixed;
+        pane.refresh()?;
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "afile.txt", "bdir", "zfile.txt"]);
+
+        // Last: directories after files
+        pane.dirs_placement = DirsPlacement::Last;
+        pane.refresh()?;
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "afile.txt", "zfile.txt", "bdir"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_column_toggle() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+
+        std::fs::write(temp_dir.path().join("big.txt"), vec![0u8; 300]).unwrap();
+        std::fs::write(temp_dir.path().join("small.txt"), vec![0u8; 10]).unwrap();
+        std::fs::write(temp_dir.path().join("medium.txt"), vec![0u8; 100]).unwrap();
+
+        let mut pane = PaneState::new(temp_dir.path().to_path_buf())?;
+
+        // First click on the size column: ascending
+        pane.toggle_sort(SortColumn::Size);
+        pane.refresh()?;
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "small.txt", "medium.txt", "big.txt"]);
+
+        // Second click on the same column reverses the order
+        pane.toggle_sort(SortColumn::Size);
+        pane.refresh()?;
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "big.txt", "medium.txt", "small.txt"]);
+
+        // Switching columns resets to ascending
+        pane.toggle_sort(SortColumn::Name);
+        pane.refresh()?;
+        let names: Vec<&str> = pane.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "big.txt", "medium.txt", "small.txt"]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_viewport_height_edge_cases() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
@@ -1108,4 +3282,37 @@ mod tests {
         
         Ok(())
     }
-} 
\ No newline at end of file
+
+    /// Property tests: no pattern, however malformed, may panic the glob
+    /// matcher, and the documented invariants must hold for any input.
+    mod glob_properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn never_panics(name in "\\PC*", pattern in "[-a-z0-9?*.\\[\\]]{0,16}") {
+                let _ = matches_glob_pattern(&name, &pattern);
+            }
+
+            #[test]
+            fn star_matches_everything(name in "\\PC*") {
+                prop_assert!(matches_glob_pattern(&name, "*"));
+            }
+
+            #[test]
+            fn literal_pattern_matches_itself(name in "[A-Za-z0-9_.]{1,20}") {
+                prop_assert!(matches_glob_pattern(&name, &name));
+            }
+
+            #[test]
+            fn extension_glob_respects_suffix(stem in "[a-z]{1,8}", ext in "[a-z]{1,4}") {
+                let name = format!("{}.{}", stem, ext);
+                let matching = format!("*.{}", ext);
+                let longer = format!("*.{}x", ext);
+                prop_assert!(matches_glob_pattern(&name, &matching));
+                prop_assert!(!matches_glob_pattern(&name, &longer));
+            }
+        }
+    }
+}
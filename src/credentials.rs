@@ -0,0 +1,267 @@
+//! Credential storage for remote backends (SFTP, WebDAV, S3, SMB).
+//!
+//! Secrets go to the OS keyring when one is reachable — secret-service via
+//! `secret-tool` on Linux, the Keychain via `security` on macOS — so they
+//! never sit in plaintext config. Where no keyring is available the secret
+//! lands in a fallback file under the state directory, written with
+//! owner-only permissions; that file is access-restricted rather than
+//! encrypted, which the docs call out so nobody mistakes it for more.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Config;
+use crate::error::{GeekCommanderError, Result};
+
+/// Where fallback credentials are persisted when no keyring is available
+pub fn credentials_file(portable: bool) -> PathBuf {
+    Config::state_dir(portable).join("credentials.toml")
+}
+
+/// Store a secret for `service` (e.g. "sftp://host") and `account`,
+/// preferring the OS keyring over the fallback file
+pub fn store_secret(fallback: &Path, service: &str, account: &str, secret: &str) -> Result<()> {
+    if keyring_store(service, account, secret) {
+        return Ok(());
+    }
+    fallback_store(fallback, service, account, secret)
+}
+
+/// Look up a previously stored secret, checking the keyring first and the
+/// fallback file second. `None` simply means nothing is stored.
+pub fn get_secret(fallback: &Path, service: &str, account: &str) -> Option<String> {
+    keyring_lookup(service, account).or_else(|| fallback_lookup(fallback, service, account))
+}
+
+/// Remove a stored secret from both the keyring and the fallback file, for
+/// the "forget credentials" command
+pub fn forget_secret(fallback: &Path, service: &str, account: &str) -> Result<()> {
+    keyring_forget(service, account);
+    fallback_forget(fallback, service, account)
+}
+
+/// How the fallback file keys a (service, account) pair
+fn fallback_key(service: &str, account: &str) -> String {
+    format!("{}\u{1f}{}", service, account)
+}
+
+fn fallback_load(fallback: &Path) -> HashMap<String, String> {
+    let content = match std::fs::read_to_string(fallback) {
+        Ok(content) => content,
+        Err(_) => return HashMap::new(),
+    };
+    toml::from_str(&content).unwrap_or_default()
+}
+
+fn fallback_save(fallback: &Path, secrets: &HashMap<String, String>) -> Result<()> {
+    use std::io::Write;
+
+    let content = toml::to_string(secrets)
+        .map_err(|e| GeekCommanderError::Config(format!("Failed to serialize credentials: {}", e)))?;
+    if let Some(parent) = fallback.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Owner-only from the first byte: the file holds secrets in the clear,
+    // so the temp file is created with mode 0600 instead of being written
+    // with umask-default permissions and chmod'ed after the fact. A stale
+    // temp file from a crashed run is removed first so its old mode cannot
+    // carry over.
+    let tmp = fallback.with_extension("toml.tmp");
+    let _ = std::fs::remove_file(&tmp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, fallback)?;
+
+    Ok(())
+}
+
+fn fallback_store(fallback: &Path, service: &str, account: &str, secret: &str) -> Result<()> {
+    let mut secrets = fallback_load(fallback);
+    secrets.insert(fallback_key(service, account), secret.to_string());
+    fallback_save(fallback, &secrets)
+}
+
+fn fallback_lookup(fallback: &Path, service: &str, account: &str) -> Option<String> {
+    fallback_load(fallback).remove(&fallback_key(service, account))
+}
+
+fn fallback_forget(fallback: &Path, service: &str, account: &str) -> Result<()> {
+    let mut secrets = fallback_load(fallback);
+    if secrets.remove(&fallback_key(service, account)).is_some() {
+        fallback_save(fallback, &secrets)?;
+    }
+    Ok(())
+}
+
+/// Try to put the secret into the OS keyring; false means "no keyring
+/// here", telling the caller to use the fallback file instead
+#[cfg(target_os = "linux")]
+fn keyring_store(service: &str, account: &str, secret: &str) -> bool {
+    use std::io::Write;
+
+    let child = Command::new("secret-tool")
+        .args([
+            "store",
+            "--label",
+            &format!("geekcommander: {}", service),
+            "service",
+            service,
+            "account",
+            account,
+        ])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+    if let Some(ref mut stdin) = child.stdin {
+        if stdin.write_all(secret.as_bytes()).is_err() {
+            return false;
+        }
+    }
+    matches!(child.wait(), Ok(status) if status.success())
+}
+
+#[cfg(target_os = "linux")]
+fn keyring_lookup(service: &str, account: &str) -> Option<String> {
+    let output = Command::new("secret-tool")
+        .args(["lookup", "service", service, "account", account])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let secret = String::from_utf8(output.stdout).ok()?;
+    let secret = secret.trim_end_matches('\n').to_string();
+    if secret.is_empty() { None } else { Some(secret) }
+}
+
+#[cfg(target_os = "linux")]
+fn keyring_forget(service: &str, account: &str) {
+    let _ = Command::new("secret-tool")
+        .args(["clear", "service", service, "account", account])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+#[cfg(target_os = "macos")]
+fn keyring_store(service: &str, account: &str, secret: &str) -> bool {
+    // -U updates an existing entry instead of failing on it
+    matches!(
+        Command::new("security")
+            .args(["add-generic-password", "-U", "-s", service, "-a", account, "-w", secret])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status(),
+        Ok(status) if status.success()
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn keyring_lookup(service: &str, account: &str) -> Option<String> {
+    let output = Command::new("security")
+        .args(["find-generic-password", "-s", service, "-a", account, "-w"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let secret = String::from_utf8(output.stdout).ok()?;
+    let secret = secret.trim_end_matches('\n').to_string();
+    if secret.is_empty() { None } else { Some(secret) }
+}
+
+#[cfg(target_os = "macos")]
+fn keyring_forget(service: &str, account: &str) {
+    let _ = Command::new("security")
+        .args(["delete-generic-password", "-s", service, "-a", account])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+// No retrievable generic-secret CLI ships with Windows (cmdkey cannot read
+// secrets back), so only the fallback file is used there
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn keyring_store(_service: &str, _account: &str, _secret: &str) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn keyring_lookup(_service: &str, _account: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn keyring_forget(_service: &str, _account: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fallback_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("credentials.toml");
+
+        fallback_store(&file, "sftp://host", "alice", "s3cret")?;
+        fallback_store(&file, "sftp://host", "bob", "other")?;
+        assert_eq!(
+            fallback_lookup(&file, "sftp://host", "alice"),
+            Some("s3cret".to_string())
+        );
+
+        fallback_forget(&file, "sftp://host", "alice")?;
+        assert_eq!(fallback_lookup(&file, "sftp://host", "alice"), None);
+        // Forgetting one account leaves the others alone
+        assert_eq!(
+            fallback_lookup(&file, "sftp://host", "bob"),
+            Some("other".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fallback_file_is_owner_only() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("credentials.toml");
+        fallback_store(&file, "webdav://host", "alice", "s3cret")?;
+
+        let mode = std::fs::metadata(&file)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_forget_on_missing_file_is_fine() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("absent.toml");
+        forget_secret(&file, "smb://host", "nobody")?;
+        assert!(!file.exists());
+        Ok(())
+    }
+}
@@ -43,4 +43,17 @@ pub enum GeekCommanderError {
 
     #[error("Terminal error: {0}")]
     Terminal(String),
-} 
\ No newline at end of file
+}
+
+impl GeekCommanderError {
+    /// Shorthand for building an [`GeekCommanderError::Archive`] error
+    pub fn archive(message: impl Into<String>) -> Self {
+        GeekCommanderError::Archive(message.into())
+    }
+}
+
+impl From<zip::result::ZipError> for GeekCommanderError {
+    fn from(error: zip::result::ZipError) -> Self {
+        GeekCommanderError::Archive(error.to_string())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+//! Application modules exposed as a library so benchmarks and integration
+//! tests can drive them directly; the binary in `main.rs` is a thin CLI
+//! wrapper around these.
+
+pub mod archive;
+pub mod batch;
+pub mod config;
+pub mod core;
+pub mod credentials;
+pub mod error;
+pub mod platform;
+pub mod sync;
+pub mod ui;
+pub mod viewer;
@@ -1,15 +1,35 @@
+use clap::Parser;
 use log::info;
 
-mod config;
-mod error;
-mod core;
-mod ui;
-mod platform;
-mod viewer;
+use geekcommander::batch;
+use geekcommander::config::Config;
+use geekcommander::error::{self, Result};
+use geekcommander::ui::App;
 
-use ui::App;
-use config::Config;
-use error::Result;
+/// A cross-platform Norton Commander-style dual-pane file manager for the terminal
+#[derive(Parser)]
+#[command(name = "geekcommander", version, about)]
+struct Cli {
+    /// Use an explicit config file instead of the default location
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Keep config, logs and state beside the executable
+    #[arg(long)]
+    portable: bool,
+
+    /// Validate the config file, report all problems, and exit
+    #[arg(long)]
+    check_config: bool,
+
+    /// Execute a command script from stdin instead of starting the TUI
+    #[arg(long)]
+    batch: bool,
+
+    /// Remove stored credentials for a remote backend and exit
+    #[arg(long, num_args = 2, value_names = ["SERVICE", "ACCOUNT"])]
+    forget_credentials: Option<Vec<String>>,
+}
 
 /// Main entry point for Geek Commander
 fn main() -> Result<()> {
@@ -31,10 +51,41 @@ fn main() -> Result<()> {
 
     info!("Starting Geek Commander");
 
-    // Load configuration
-    let config = Config::load_or_create_default(None)?;
-    
+    let cli = Cli::parse();
+
+    // Load configuration, collecting every problem instead of failing fast
+    let (config, problems) = Config::load_with_report(cli.config.as_deref(), cli.portable);
+
+    if cli.check_config {
+        if problems.is_empty() {
+            println!("Configuration OK");
+            return Ok(());
+        }
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        std::process::exit(1);
+    }
+
+    if let Some(args) = &cli.forget_credentials {
+        let (service, account) = (&args[0], &args[1]);
+        let fallback = geekcommander::credentials::credentials_file(cli.portable);
+        geekcommander::credentials::forget_secret(&fallback, service, account)?;
+        println!("Forgot credentials for {} ({})", service, account);
+        return Ok(());
+    }
+
+    if cli.batch {
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        return batch::run(&config);
+    }
+
     // Create and run the application
     let mut app = App::new(config)?;
+    if !problems.is_empty() {
+        app.show_config_problems(&problems);
+    }
     app.run()
 } 
\ No newline at end of file
@@ -33,9 +33,28 @@ pub fn get_free_disk_space(path: &Path) -> Result<u64> {
         }
     }
     
-    #[cfg(not(windows))]
+    #[cfg(unix)]
     {
-        // Simplified fallback for non-Windows
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| GeekCommanderError::FileOperation("Path contains a NUL byte".to_string()))?;
+
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        let result = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+
+        if result == 0 {
+            // Field widths differ between platforms, hence the casts
+            #[allow(clippy::unnecessary_cast)]
+            Ok((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+        } else {
+            Err(GeekCommanderError::Io(std::io::Error::last_os_error()))
+        }
+    }
+
+    #[cfg(not(any(windows, unix)))]
+    {
+        // Simplified fallback for other platforms
         Ok(1024 * 1024 * 1024) // Return 1GB as fallback
     }
 }
@@ -105,6 +124,26 @@ pub fn format_file_size(size: u64) -> String {
     }
 }
 
+/// Shorten a string to at most `max_chars` characters by replacing the
+/// middle with an ellipsis, keeping the start and end visible. Useful for
+/// fitting deep paths into single-line areas like the status bar.
+pub fn middle_truncate(text: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars <= 1 {
+        return "…".to_string();
+    }
+    let keep = max_chars - 1;
+    let head = keep / 2;
+    let tail = keep - head;
+    let mut result: String = chars[..head].iter().collect();
+    result.push('…');
+    result.extend(&chars[chars.len() - tail..]);
+    result
+}
+
 /// Check if a file name should be considered hidden
 pub fn is_hidden_file(name: &str) -> bool {
     name.starts_with('.')
@@ -191,6 +230,516 @@ pub fn get_file_permissions(metadata: &std::fs::Metadata) -> String {
     }
 }
 
+/// Convert a symbolic permission string from [`get_file_permissions`] (with
+/// or without the leading file-type character) to octal, e.g. `rwxr-xr--`
+/// to `754`. Anything that is not rwx triads comes back unchanged.
+pub fn permissions_octal(permissions: &str) -> String {
+    let chars: Vec<char> = permissions.chars().collect();
+    let triads = match chars.len() {
+        9 => &chars[..],
+        10 => &chars[1..],
+        _ => return permissions.to_string(),
+    };
+
+    let mut octal = String::with_capacity(3);
+    for triad in triads.chunks(3) {
+        // A string of the right length that is not actually rwx triads
+        // (e.g. "read-only") must not produce nonsense digits
+        if !matches!(triad[0], 'r' | '-')
+            || !matches!(triad[1], 'w' | '-')
+            || !matches!(triad[2], 'x' | 's' | 't' | 'S' | 'T' | '-')
+        {
+            return permissions.to_string();
+        }
+        let mut digit = 0;
+        if triad[0] == 'r' {
+            digit += 4;
+        }
+        if triad[1] == 'w' {
+            digit += 2;
+        }
+        // 's' and 't' carry the execute bit alongside setuid/setgid/sticky
+        if matches!(triad[2], 'x' | 's' | 't') {
+            digit += 1;
+        }
+        octal.push(char::from_digit(digit, 8).unwrap_or('0'));
+    }
+    octal
+}
+
+/// A mounted (or mountable) drive as shown in the drive selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveInfo {
+    pub device: String,
+    pub mount_point: Option<PathBuf>,
+    pub removable: bool,
+}
+
+/// List drives known to the system.
+///
+/// On Linux this parses `/proc/mounts` for real block devices and checks
+/// `/sys/block/<disk>/removable` to flag removable media. Other platforms get
+/// a minimal fallback listing the filesystem root(s).
+pub fn list_drives() -> Vec<DriveInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut drives = Vec::new();
+
+        if let Ok(mounts) = std::fs::read_to_string("/proc/mounts") {
+            for line in mounts.lines() {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 2 || !fields[0].starts_with("/dev/") {
+                    continue;
+                }
+
+                let device = fields[0].to_string();
+                // Mount points in /proc/mounts escape spaces as \040
+                let mount_point = PathBuf::from(fields[1].replace("\\040", " "));
+
+                drives.push(DriveInfo {
+                    removable: is_removable_device(&device),
+                    device,
+                    mount_point: Some(mount_point),
+                });
+            }
+        }
+
+        if drives.is_empty() {
+            drives.push(DriveInfo {
+                device: "/".to_string(),
+                mount_point: Some(PathBuf::from("/")),
+                removable: false,
+            });
+        }
+
+        drives
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut drives = Vec::new();
+
+        #[cfg(windows)]
+        for letter in b'A'..=b'Z' {
+            let root = format!("{}:\\", letter as char);
+            if Path::new(&root).exists() {
+                drives.push(DriveInfo {
+                    device: root.clone(),
+                    mount_point: Some(PathBuf::from(root)),
+                    removable: false,
+                });
+            }
+        }
+
+        #[cfg(not(windows))]
+        drives.push(DriveInfo {
+            device: "/".to_string(),
+            mount_point: Some(PathBuf::from("/")),
+            removable: false,
+        });
+
+        drives
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &str) -> bool {
+    // Strip /dev/ prefix and trailing partition number (sda1 -> sda, nvme0n1p2 -> nvme0n1)
+    let name = device.trim_start_matches("/dev/");
+    let disk = if name.contains("nvme") {
+        name.split('p').next().unwrap_or(name)
+    } else {
+        name.trim_end_matches(|c: char| c.is_ascii_digit())
+    };
+
+    std::fs::read_to_string(format!("/sys/block/{}/removable", disk))
+        .map(|content| content.trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Run a `udisksctl` subcommand against a block device (Linux only)
+#[cfg(target_os = "linux")]
+fn run_udisksctl(subcommand: &str, device: &str) -> Result<String> {
+    let output = std::process::Command::new("udisksctl")
+        .arg(subcommand)
+        .arg("-b")
+        .arg(device)
+        .output()
+        .map_err(|e| GeekCommanderError::FileOperation(format!("Failed to run udisksctl: {}", e)))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(GeekCommanderError::FileOperation(format!(
+            "udisksctl {} failed: {}",
+            subcommand,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Mount a removable drive via udisks2, returning the status message
+pub fn mount_drive(device: &str) -> Result<String> {
+    #[cfg(target_os = "linux")]
+    { run_udisksctl("mount", device) }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = device;
+        Err(GeekCommanderError::FileOperation("Mounting is only supported on Linux".to_string()))
+    }
+}
+
+/// Unmount a removable drive via udisks2, returning the status message
+pub fn unmount_drive(device: &str) -> Result<String> {
+    #[cfg(target_os = "linux")]
+    { run_udisksctl("unmount", device) }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = device;
+        Err(GeekCommanderError::FileOperation("Unmounting is only supported on Linux".to_string()))
+    }
+}
+
+/// Eject removable media via udisks2, returning the status message
+pub fn eject_drive(device: &str) -> Result<String> {
+    #[cfg(target_os = "linux")]
+    { run_udisksctl("power-off", device) }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = device;
+        Err(GeekCommanderError::FileOperation("Ejecting is only supported on Linux".to_string()))
+    }
+}
+
+/// Open a file or directory with the application the OS associates with it.
+/// On Windows this goes through `ShellExecuteW`, so Explorer's file
+/// associations apply without any extra configuration.
+pub fn open_with_default_app(path: &Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::shellapi::ShellExecuteW;
+        use winapi::um::winuser::SW_SHOWNORMAL;
+
+        let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let verb: Vec<u16> = "open".encode_utf16().chain(std::iter::once(0)).collect();
+        let result = unsafe {
+            ShellExecuteW(
+                std::ptr::null_mut(),
+                verb.as_ptr(),
+                wide_path.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+        // Per the ShellExecute documentation, values above 32 indicate success
+        if result as usize <= 32 {
+            return Err(GeekCommanderError::FileOperation(format!(
+                "No application is associated with '{}' (ShellExecute code {})",
+                path.display(),
+                result as usize
+            )));
+        }
+        return Ok(());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        #[cfg(target_os = "macos")]
+        let mut command = {
+            let mut cmd = std::process::Command::new("open");
+            cmd.arg(path);
+            cmd
+        };
+
+        #[cfg(not(target_os = "macos"))]
+        let mut command = {
+            let mut cmd = std::process::Command::new("xdg-open");
+            cmd.arg(path);
+            cmd
+        };
+
+        command
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|e| GeekCommanderError::FileOperation(format!("Failed to launch opener: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Bytes of disk space actually allocated to a file, which is less than the
+/// logical size for sparse files. `None` where the platform cannot tell.
+pub fn allocated_file_size(path: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // st_blocks counts 512-byte units regardless of the filesystem block size
+        std::fs::metadata(path).ok().map(|m| m.blocks() * 512)
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::fileapi::GetCompressedFileSizeW;
+        use winapi::um::winbase::INVALID_FILE_SIZE;
+
+        let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut high: u32 = 0;
+        let low = unsafe { GetCompressedFileSizeW(wide_path.as_ptr(), &mut high) };
+        if low == INVALID_FILE_SIZE {
+            return None;
+        }
+        Some(((high as u64) << 32) | low as u64)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Whether a file occupies noticeably less disk space than its logical size
+pub fn is_sparse_file(path: &Path) -> bool {
+    match (allocated_file_size(path), std::fs::metadata(path)) {
+        (Some(allocated), Ok(metadata)) => allocated < metadata.len(),
+        _ => false,
+    }
+}
+
+/// List a file's NTFS alternate data streams as (name, size) pairs, with
+/// the unnamed `::$DATA` stream excluded. Non-Windows platforms and volumes
+/// without stream support report none.
+pub fn list_alternate_data_streams(path: &Path) -> Vec<(String, u64)> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::fileapi::{FindClose, FindFirstStreamW, FindNextStreamW};
+        use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+        use winapi::um::minwinbase::{FindStreamInfoStandard, WIN32_FIND_STREAM_DATA};
+
+        let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut data: WIN32_FIND_STREAM_DATA = unsafe { std::mem::zeroed() };
+        let handle = unsafe {
+            FindFirstStreamW(wide_path.as_ptr(), FindStreamInfoStandard, &mut data as *mut _ as *mut _, 0)
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Vec::new();
+        }
+
+        let mut streams = Vec::new();
+        loop {
+            let len = data.cStreamName.iter().position(|&c| c == 0).unwrap_or(0);
+            let raw = String::from_utf16_lossy(&data.cStreamName[..len]);
+            // Stream names come back as ":name:$DATA"; the main data stream
+            // is "::$DATA" and is not interesting here
+            if raw != "::$DATA" {
+                let name = raw
+                    .strip_prefix(':')
+                    .and_then(|s| s.strip_suffix(":$DATA"))
+                    .unwrap_or(&raw)
+                    .to_string();
+                let size = unsafe { *data.StreamSize.QuadPart() } as u64;
+                streams.push((name, size));
+            }
+            if unsafe { FindNextStreamW(handle, &mut data as *mut _ as *mut _) } == 0 {
+                break;
+            }
+        }
+        unsafe { FindClose(handle) };
+        streams
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+/// Copy a file's named alternate data streams to the destination, which must
+/// already hold the main data. Returns the names of streams that could not
+/// be copied. A no-op where streams do not exist.
+pub fn copy_alternate_data_streams(source: &Path, dest: &Path) -> Vec<String> {
+    let mut failed = Vec::new();
+    for (name, _) in list_alternate_data_streams(source) {
+        let mut stream_source = source.as_os_str().to_os_string();
+        stream_source.push(format!(":{}", name));
+        let mut stream_dest = dest.as_os_str().to_os_string();
+        stream_dest.push(format!(":{}", name));
+        if std::fs::copy(&stream_source, &stream_dest).is_err() {
+            failed.push(name);
+        }
+    }
+    failed
+}
+
+/// Set a file's modification time to the given Unix timestamp. Best effort:
+/// platforms without an implementation keep the current time.
+pub fn set_file_mtime(path: &Path, seconds: i64) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| GeekCommanderError::FileOperation("Path contains a NUL byte".to_string()))?;
+        let times = [libc::timespec { tv_sec: seconds as libc::time_t, tv_nsec: 0 }; 2];
+        let rc = unsafe { libc::utimensat(libc::AT_FDCWD, c_path.as_ptr(), times.as_ptr(), 0) };
+        if rc != 0 {
+            return Err(GeekCommanderError::FileOperation(format!(
+                "Failed to set mtime: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (path, seconds);
+        Ok(())
+    }
+}
+
+/// Put text on the system clipboard by piping it to the platform's
+/// clipboard tool
+pub fn copy_text_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+
+    #[cfg(target_os = "windows")]
+    let mut command = std::process::Command::new("clip");
+
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("pbcopy");
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut cmd = std::process::Command::new("xclip");
+        cmd.arg("-selection").arg("clipboard");
+        cmd
+    };
+
+    let mut child = command
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| GeekCommanderError::FileOperation(format!("No clipboard tool available: {}", e)))?;
+
+    if let Some(ref mut stdin) = child.stdin {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| GeekCommanderError::FileOperation(format!("Failed to write to clipboard: {}", e)))?;
+    }
+    drop(child.stdin.take());
+
+    let status = child
+        .wait()
+        .map_err(|e| GeekCommanderError::FileOperation(format!("Clipboard tool failed: {}", e)))?;
+    if !status.success() {
+        return Err(GeekCommanderError::FileOperation(
+            "Clipboard tool exited with an error".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Write a file crash-safely: the content goes to a temp file in the same
+/// directory which is then renamed over the original, keeping one `.bak`
+/// generation of the previous content. Used for config and session/state files.
+pub fn atomic_write(path: &Path, content: &str) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "file".to_string());
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let tmp_path = parent.join(format!("{}.tmp", file_name));
+    std::fs::write(&tmp_path, content)?;
+
+    if path.exists() {
+        let bak_path = parent.join(format!("{}.bak", file_name));
+        let _ = std::fs::rename(path, &bak_path);
+    }
+
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Get the hard link count for a file (1 on platforms without the metadata)
+pub fn get_link_count(metadata: &std::fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.nlink()
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        1
+    }
+}
+
+/// Get the device and inode identifying a file (None on platforms without inodes)
+pub fn get_file_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Run `command` through the platform shell with `dir` as the working
+/// directory, capturing its output for display in a dialog
+pub fn run_shell_command(command: &str, dir: &Path) -> std::io::Result<std::process::Output> {
+    #[cfg(windows)]
+    {
+        std::process::Command::new("cmd")
+            .arg("/C")
+            .arg(command)
+            .current_dir(dir)
+            .output()
+    }
+    #[cfg(not(windows))]
+    {
+        std::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(dir)
+            .output()
+    }
+}
+
+/// Whether the filesystem holding `dir` ignores letter case in file names
+/// (Windows, default APFS). Probed by creating a lowercase marker file and
+/// looking it up with the case swapped, since a volume's behavior can differ
+/// from the platform default (e.g. a FAT USB stick mounted on Linux); when
+/// the probe cannot be created, fall back to the platform default.
+pub fn is_case_insensitive_fs(dir: &Path) -> bool {
+    let pid = std::process::id();
+    let probe = dir.join(format!(".case-probe-{}", pid));
+    let swapped = dir.join(format!(".CASE-PROBE-{}", pid));
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            let insensitive = swapped.exists();
+            let _ = std::fs::remove_file(&probe);
+            insensitive
+        },
+        Err(_) => cfg!(any(windows, target_os = "macos")),
+    }
+}
+
 /// Format file modification time for display (Norton Commander style)
 pub fn format_file_time(system_time: std::time::SystemTime) -> String {
     use chrono::{DateTime, Local};
@@ -213,6 +762,41 @@ mod tests {
         assert_eq!(format_file_size(1024 * 1024 * 1024), "1.0 GB");
     }
 
+    #[test]
+    fn test_middle_truncate() {
+        assert_eq!(middle_truncate("short", 10), "short");
+        assert_eq!(middle_truncate("/very/deep/nested/path", 11), "/very…/path");
+        assert_eq!(middle_truncate("abcdef", 1), "…");
+        // Counts characters, not bytes
+        assert_eq!(middle_truncate("ééééééé", 5), "éé…éé");
+    }
+
+    #[test]
+    fn test_permissions_octal() {
+        assert_eq!(permissions_octal("-rwxr-xr--"), "754");
+        assert_eq!(permissions_octal("drwxr-xr-x"), "755");
+        // Windows strings have no file-type character
+        assert_eq!(permissions_octal("rw-rw-rw-"), "666");
+        // setuid/sticky imply the execute bit
+        assert_eq!(permissions_octal("-rwsr-xr-t"), "755");
+        // Anything else passes through untouched
+        assert_eq!(permissions_octal("read-only"), "read-only");
+    }
+
+    #[test]
+    fn test_is_case_insensitive_fs_matches_lookup_behavior() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("witness.txt"), "x").unwrap();
+
+        // The probe must agree with how the filesystem actually resolves
+        // a case-swapped name, whatever platform the test runs on
+        let expected = temp_dir.path().join("WITNESS.TXT").exists();
+        assert_eq!(is_case_insensitive_fs(temp_dir.path()), expected);
+        // The probe file must not be left behind
+        let leftovers = std::fs::read_dir(temp_dir.path()).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+
     #[test]
     fn test_is_hidden_file() {
         assert!(is_hidden_file(".hidden"));
@@ -262,6 +846,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_atomic_write() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let target = temp_dir.path().join("config");
+
+        // First write: no backup yet
+        atomic_write(&target, "first").unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "first");
+        assert!(!temp_dir.path().join("config.bak").exists());
+
+        // Second write: previous content lands in the .bak generation
+        atomic_write(&target, "second").unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "second");
+        assert_eq!(std::fs::read_to_string(temp_dir.path().join("config.bak")).unwrap(), "first");
+
+        // No temp file left behind
+        assert!(!temp_dir.path().join("config.tmp").exists());
+    }
+
     #[test]
     fn test_format_file_time() {
         use std::time::{SystemTime, UNIX_EPOCH, Duration};
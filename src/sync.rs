@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::error::Result;
+
+/// How file pairs are compared when syncing two directories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareMode {
+    /// Compare sizes only (fast, misses same-size edits)
+    SizeOnly,
+    /// Compare size and modification time
+    SizeMtime,
+    /// Compare full file contents via a hash (slow, accurate)
+    ContentHash,
+}
+
+impl CompareMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompareMode::SizeOnly => "size only",
+            CompareMode::SizeMtime => "size + mtime",
+            CompareMode::ContentHash => "content hash",
+        }
+    }
+}
+
+/// A single discrepancy between the two sides, with the path relative to the
+/// compared roots
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    OnlyInLeft(PathBuf),
+    OnlyInRight(PathBuf),
+    Differs(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncReport {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    /// The mode this comparison ran with, so the report is self-describing
+    pub mode: CompareMode,
+    pub differences: Vec<Difference>,
+    pub files_compared: usize,
+}
+
+impl SyncReport {
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Compared {} <-> {}\nMode: {}\nFiles compared: {}\n",
+            self.left.display(),
+            self.right.display(),
+            self.mode.label(),
+            self.files_compared,
+        );
+
+        if self.differences.is_empty() {
+            text.push_str("\nNo differences found");
+            return text;
+        }
+
+        text.push_str(&format!("\n{} difference(s):\n", self.differences.len()));
+        for difference in &self.differences {
+            match difference {
+                Difference::OnlyInLeft(path) => text.push_str(&format!("  < only left:  {}\n", path.display())),
+                Difference::OnlyInRight(path) => text.push_str(&format!("  > only right: {}\n", path.display())),
+                Difference::Differs(path) => text.push_str(&format!("  ! differs:    {}\n", path.display())),
+            }
+        }
+        text
+    }
+}
+
+/// Compare the files under two directories recursively, using the given
+/// tolerance mode
+pub fn compare_directories(left: &Path, right: &Path, mode: CompareMode) -> Result<SyncReport> {
+    let left_files = collect_files(left)?;
+    let right_files = collect_files(right)?;
+    let mut differences = Vec::new();
+    let mut files_compared = 0;
+
+    for (relative, left_meta) in &left_files {
+        match right_files.get(relative) {
+            Some(right_meta) => {
+                files_compared += 1;
+                if !files_match(left, right, relative, left_meta, right_meta, mode)? {
+                    differences.push(Difference::Differs(relative.clone()));
+                }
+            },
+            None => differences.push(Difference::OnlyInLeft(relative.clone())),
+        }
+    }
+
+    for relative in right_files.keys() {
+        if !left_files.contains_key(relative) {
+            differences.push(Difference::OnlyInRight(relative.clone()));
+        }
+    }
+
+    Ok(SyncReport {
+        left: left.to_path_buf(),
+        right: right.to_path_buf(),
+        mode,
+        differences,
+        files_compared,
+    })
+}
+
+fn files_match(
+    left_root: &Path,
+    right_root: &Path,
+    relative: &Path,
+    left: &(u64, SystemTime),
+    right: &(u64, SystemTime),
+    mode: CompareMode,
+) -> Result<bool> {
+    if left.0 != right.0 {
+        return Ok(false);
+    }
+
+    match mode {
+        CompareMode::SizeOnly => Ok(true),
+        CompareMode::SizeMtime => Ok(mtimes_match(left.1, right.1)),
+        CompareMode::ContentHash => {
+            Ok(file_hash(&left_root.join(relative))? == file_hash(&right_root.join(relative))?)
+        },
+    }
+}
+
+/// Modification times within two seconds count as equal, to cope with
+/// filesystems (FAT) that store coarse timestamps
+fn mtimes_match(a: SystemTime, b: SystemTime) -> bool {
+    let delta = match a.duration_since(b) {
+        Ok(delta) => delta,
+        Err(e) => e.duration(),
+    };
+    delta.as_secs() <= 2
+}
+
+/// Map of relative file path → (size, mtime) for everything under `root`
+fn collect_files(root: &Path) -> Result<BTreeMap<PathBuf, (u64, SystemTime)>> {
+    let mut files = BTreeMap::new();
+    collect_files_recursive(root, root, &mut files)?;
+    Ok(files)
+}
+
+fn collect_files_recursive(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<PathBuf, (u64, SystemTime)>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files_recursive(root, &path, files)?;
+        } else if path.is_file() {
+            let metadata = entry.metadata()?;
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            files.insert(
+                relative,
+                (metadata.len(), metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH)),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// FNV-1a over the file contents; no cryptographic strength needed, this is
+/// only ever compared against another local file
+fn file_hash(path: &Path) -> Result<u64> {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut file = fs::File::open(path)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut hash = FNV_OFFSET;
+
+    loop {
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        for &byte in &buffer[..bytes_read] {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_pair() -> (TempDir, TempDir) {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+
+        std::fs::write(left.path().join("same.txt"), "identical").unwrap();
+        std::fs::write(right.path().join("same.txt"), "identical").unwrap();
+
+        // Same size, different content: only a content hash can tell
+        std::fs::write(left.path().join("edited.txt"), "aaaa").unwrap();
+        std::fs::write(right.path().join("edited.txt"), "bbbb").unwrap();
+
+        std::fs::write(left.path().join("left-only.txt"), "l").unwrap();
+        std::fs::write(right.path().join("right-only.txt"), "r").unwrap();
+
+        (left, right)
+    }
+
+    #[test]
+    fn test_compare_size_only_misses_same_size_edits() -> Result<()> {
+        let (left, right) = setup_pair();
+        let report = compare_directories(left.path(), right.path(), CompareMode::SizeOnly)?;
+
+        assert_eq!(report.mode, CompareMode::SizeOnly);
+        assert!(report.differences.contains(&Difference::OnlyInLeft(PathBuf::from("left-only.txt"))));
+        assert!(report.differences.contains(&Difference::OnlyInRight(PathBuf::from("right-only.txt"))));
+        assert!(!report.differences.contains(&Difference::Differs(PathBuf::from("edited.txt"))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_content_hash_finds_same_size_edits() -> Result<()> {
+        let (left, right) = setup_pair();
+        let report = compare_directories(left.path(), right.path(), CompareMode::ContentHash)?;
+
+        assert!(report.differences.contains(&Difference::Differs(PathBuf::from("edited.txt"))));
+        assert!(!report.differences.iter().any(|d| matches!(d, Difference::Differs(p) if p == Path::new("same.txt"))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_records_mode() -> Result<()> {
+        let (left, right) = setup_pair();
+        let report = compare_directories(left.path(), right.path(), CompareMode::SizeMtime)?;
+
+        assert!(report.to_text().contains("Mode: size + mtime"));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Stdout;
 use crossterm::{
-    event::{self, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -12,8 +12,8 @@ use tui::{
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Table, Row, Cell},
     Frame, Terminal,
 };
-use crate::config::Config;
-use crate::core::{PaneState, FileOperation, copy_files, move_files, delete_files, execute_operation, create_directory, rename_file, FileEntry};
+use crate::config::{Config, EnterAction, KeyBinding, NavigationStyle};
+use crate::core::{PaneState, FileOperation, copy_files_with_excludes, move_files, delete_files, spawn_operation, create_directories, rename_file, directory_stats, is_directory_empty, scan_total_size_background, spawn_dir_size_scan, spawn_dir_count_scan, spawn_directory_summary, save_operation_state, load_operation_state, clear_operation_state, save_recent_files, load_recent_files, build_panel_report, git_changed_files, expand_command_variables, resolve_start_path, FileEntry, SortColumn};
 use crate::error::{GeekCommanderError, Result};
 use crate::viewer::{FileViewer, launch_external_editor};
 use crate::platform;
@@ -25,14 +25,51 @@ pub enum DialogType {
     Input { prompt: String, input: String, action: InputAction },
     Progress { operation: FileOperation },
     Error { message: String },
+    /// F10 while a background operation runs: wait, cancel it, or stay
+    QuitWithJobs { message: String },
+    DriveSelect { drives: Vec<platform::DriveInfo>, selected: usize },
+    Scanning { scanned: u64 },
+    Info { title: String, message: String },
+    ContextMenu { selected: usize, x: u16, y: u16 },
+    HotPath { ancestors: Vec<std::path::PathBuf>, selected: usize },
+    /// Alt+R: recently viewed/edited/opened files, newest first
+    RecentFiles { files: Vec<std::path::PathBuf>, selected: usize },
+    CompareModeSelect { selected: usize },
 }
 
+/// Comparison modes offered by the pane-compare dialog, in display order
+const COMPARE_MODES: [crate::sync::CompareMode; 3] = [
+    crate::sync::CompareMode::SizeOnly,
+    crate::sync::CompareMode::SizeMtime,
+    crate::sync::CompareMode::ContentHash,
+];
+
+/// How long a toast message stays on the status line
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(4);
+
+/// How many files the persisted recent-files history keeps
+const RECENT_FILES_LIMIT: usize = 20;
+
+/// Entries of the right-click / F9 context menu, in display order
+const CONTEXT_MENU_ITEMS: [&str; 9] = [
+    "View", "Edit", "Copy", "Move", "Delete", "Rename", "Properties", "Open With", "Zip Comment",
+];
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ConfirmAction {
     Copy,
     Move,
     Delete,
+    DeleteRecursive,
     Overwrite,
+    /// Run the pending operation even though the destination looks too small
+    ProceedLowSpace,
+    /// Run the pending operation past the overwrite/bulk preflight warnings
+    ProceedChecks,
+    /// Resume an operation persisted before the last exit or crash
+    ResumeOperation,
+    /// Show the file as a hex dump after a size or binary-content rejection
+    ViewAsHex(std::path::PathBuf),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -40,6 +77,15 @@ pub enum InputAction {
     NewDirectory,
     Rename,
     SelectByPattern,
+    CopyExcludes,
+    /// Alt+P: file the side-by-side panel report is written to
+    ExportPanelReport,
+    /// Alt+G: git ref whose changed files get selected
+    SelectGitChanged,
+    /// New archive comment for the ZIP under the cursor
+    EditZipComment,
+    /// Alt+O: shell command with `%l`/`%r`/`%L`/`%R`/`%f` pane variables
+    RunCommand,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -48,63 +94,123 @@ pub enum AppMode {
     Viewer,
 }
 
-pub struct App {
+/// Whether pasted clipboard entries are copied or moved
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipboardMode {
+    Copy,
+    Cut,
+}
+
+pub struct App<B: tui::backend::Backend = CrosstermBackend<Stdout>> {
     pub config: Config,
     pub left_pane: PaneState,
     pub right_pane: PaneState,
     pub active_pane: usize,
-    pub terminal: Terminal<CrosstermBackend<Stdout>>,
+    pub terminal: Terminal<B>,
     pub current_dialog: Option<DialogType>,
     pub should_quit: bool,
     pub mode: AppMode,
     pub viewer: Option<FileViewer>,
+    pending_delete_stats: Option<std::sync::mpsc::Receiver<(u64, u64)>>,
+    clipboard: Option<(ClipboardMode, Vec<std::path::PathBuf>)>,
+    dragging_splitter: bool,
+    /// Shared state of the operation currently running on a worker thread
+    active_operation: Option<std::sync::Arc<std::sync::Mutex<FileOperation>>>,
+    operation_handle: Option<std::thread::JoinHandle<Result<()>>>,
+    /// Operation held back by the free-space warning, awaiting confirmation
+    pending_operation: Option<FileOperation>,
+    /// Recursive directory sizes computed so far, keyed by full path
+    dir_size_cache: std::collections::HashMap<std::path::PathBuf, u64>,
+    dir_size_rx: Option<std::sync::mpsc::Receiver<(std::path::PathBuf, u64)>>,
+    /// Set to stop the current background size walk on navigation
+    dir_size_cancel: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// The pane paths the last size scan was started for
+    dir_size_paths: Option<(std::path::PathBuf, std::path::PathBuf)>,
+    /// Immediate child counts computed so far, keyed by full path
+    dir_count_cache: std::collections::HashMap<std::path::PathBuf, u64>,
+    dir_count_rx: Option<std::sync::mpsc::Receiver<(std::path::PathBuf, u64)>>,
+    /// Set to stop the current background count scan on navigation
+    dir_count_cancel: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// The pane paths the last count scan was started for
+    dir_count_paths: Option<(std::path::PathBuf, std::path::PathBuf)>,
+    /// Delivers the panes' initial directory listings, read on background
+    /// threads at startup; dropped once both have arrived
+    pane_load_rx: Option<std::sync::mpsc::Receiver<(usize, Result<PaneState>)>>,
+    /// Where unfinished operations are persisted for resume after a restart
+    operation_state_file: std::path::PathBuf,
+    /// Alt+X: show the permissions column in octal instead of rwx triads
+    octal_permissions: bool,
+    /// When the panes were last polled by the periodic auto-refresh
+    last_auto_refresh: std::time::Instant,
+    /// Recently viewed/edited/opened files, newest first, shown by Alt+R
+    recent_files: Vec<std::path::PathBuf>,
+    /// Where the recent-files history is persisted across sessions
+    recent_files_file: std::path::PathBuf,
+    /// Vertical scroll position of the current dialog's content
+    dialog_scroll: u16,
+    /// Directory summary being generated for the viewer (F3 on a directory)
+    dir_summary_rx: Option<std::sync::mpsc::Receiver<String>>,
+    /// The directory the pending summary is for, shown as the viewer title
+    dir_summary_path: Option<std::path::PathBuf>,
+    /// Transient non-modal message shown on the status line until it expires
+    toast: Option<(String, std::time::Instant)>,
+    /// Timestamped record of past toast messages, viewable with Alt+M
+    message_history: Vec<String>,
+    /// Quit as soon as the running operation finishes (W in the quit dialog)
+    quit_when_idle: bool,
 }
 
 impl App {
     pub fn new(config: Config) -> Result<Self> {
         enable_raw_mode()?;
         let mut stdout = std::io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
-
-        let left_start = config.panels.left.clone();
-        let right_start = config.panels.right.clone();
-
-        let left_pane = PaneState::new(left_start)?;
-        let right_pane = PaneState::new(right_start)?;
-
-        Ok(App {
-            config,
-            left_pane,
-            right_pane,
-            active_pane: 0,
-            terminal,
-            current_dialog: None,
-            should_quit: false,
-            mode: AppMode::Normal,
-            viewer: None,
-        })
+        Self::with_terminal(config, terminal)
     }
 
     pub fn run(&mut self) -> Result<()> {
         loop {
             self.draw()?;
-            
+
             if self.should_quit {
                 break;
             }
-            
+
+            // Let an expired toast fall back to the normal status line
+            if let Some((_, shown)) = &self.toast {
+                if shown.elapsed() >= TOAST_DURATION {
+                    self.toast = None;
+                }
+            }
+
+            self.poll_pane_loads();
+            self.poll_delete_stats();
+            self.poll_operation()?;
+            self.maybe_request_dir_sizes();
+            self.poll_dir_sizes();
+            self.maybe_request_dir_counts();
+            self.poll_dir_counts();
+            self.poll_dir_summary();
+            self.maybe_auto_refresh();
+
             // Check for events with a small timeout
             if let Ok(true) = event::poll(std::time::Duration::from_millis(50)) {
                 if let Ok(event) = event::read() {
-                    if let event::Event::Key(key) = event {
-                        // Only process key press events, ignore key repeat and release
-                        if key.kind == KeyEventKind::Press {
-                            self.handle_key_event(key.code, key.modifiers)?;
-                            // Small delay to prevent key repeat flooding
-                            std::thread::sleep(std::time::Duration::from_millis(10));
-                        }
+                    match event {
+                        event::Event::Key(key) => {
+                            // Only process key press events, ignore key repeat and release
+                            if key.kind == KeyEventKind::Press {
+                                self.handle_key_event(key.code, key.modifiers)?;
+                                // Small delay to prevent key repeat flooding
+                                std::thread::sleep(std::time::Duration::from_millis(10));
+                            }
+                        },
+                        event::Event::Mouse(mouse) => {
+                            self.handle_mouse_event(mouse)?;
+                        },
+                        _ => {}
                     }
                 }
             }
@@ -113,7 +219,126 @@ impl App {
         self.cleanup()
     }
 
+    fn cleanup(&mut self) -> Result<()> {
+        disable_raw_mode()?;
+        execute!(self.terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+        self.terminal.show_cursor()?;
+        Ok(())
+    }
+}
+
+impl<B: tui::backend::Backend> App<B> {
+    /// Build the application state on an already-created terminal. Split
+    /// from `new` so tests can drive the UI on a headless `TestBackend`.
+    fn with_terminal(config: Config, terminal: Terminal<B>) -> Result<Self> {
+        // Configured paths may point at unplugged drives or deleted folders;
+        // fall back rather than failing to start
+        let (left_start, left_notice) = resolve_start_path(config.panels.left.clone());
+        let (right_start, right_notice) = resolve_start_path(config.panels.right.clone());
+
+        // Read the start directories from background threads so the UI
+        // appears immediately even when they live on a slow network mount;
+        // each pane shows a spinner until its listing lands
+        let (pane_load_tx, pane_load_rx) = std::sync::mpsc::channel();
+        let mut panes = Vec::new();
+        for (index, start) in [(0usize, left_start), (1, right_start)] {
+            let mut pane = PaneState::new_loading(start);
+            pane.dirs_placement = config.general.dirs_placement;
+            pane.case_sensitivity = config.general.case_sensitivity;
+            let mut worker = pane.clone();
+            let tx = pane_load_tx.clone();
+            std::thread::spawn(move || {
+                let result = worker.refresh().map(|_| worker);
+                let _ = tx.send((index, result));
+            });
+            panes.push(pane);
+        }
+        let left_pane = panes.remove(0);
+        let right_pane = panes.remove(0);
+
+        let recent_files_file = config.resolved_state_dir().join("recent-files.txt");
+        let recent_files = load_recent_files(&recent_files_file);
+
+        // Offer to resume an operation persisted before the last exit/crash
+        let operation_state_file = config.resolved_state_dir().join("pending-operation.toml");
+        let mut pending_operation = None;
+        let mut current_dialog = None;
+        if let Some(mut operation) = load_operation_state(&operation_state_file) {
+            // Progress starts over; the resume flag skips files that already
+            // arrived in full at the destination
+            operation.processed_size = 0;
+            operation.files_completed = 0;
+            operation.current_file = None;
+            operation.current_file_size = 0;
+            operation.current_file_processed = 0;
+            operation.completed = false;
+            operation.cancelled = false;
+            operation.resume = true;
+            current_dialog = Some(DialogType::Confirm {
+                message: format!(
+                    "An interrupted {:?} operation ({} item(s) to {}) was found.\nResume it now?",
+                    operation.operation_type,
+                    operation.source_files.len(),
+                    operation.destination.display()
+                ),
+                action: ConfirmAction::ResumeOperation,
+            });
+            pending_operation = Some(operation);
+        }
+
+        // The resume prompt takes precedence over path fallback notices
+        if current_dialog.is_none() {
+            if let Some(message) = left_notice.or(right_notice) {
+                current_dialog = Some(DialogType::Error { message });
+            }
+        }
+
+        Ok(App {
+            config,
+            left_pane,
+            right_pane,
+            active_pane: 0,
+            terminal,
+            current_dialog,
+            should_quit: false,
+            mode: AppMode::Normal,
+            viewer: None,
+            pending_delete_stats: None,
+            clipboard: None,
+            dragging_splitter: false,
+            active_operation: None,
+            operation_handle: None,
+            pending_operation,
+            dir_size_cache: std::collections::HashMap::new(),
+            dir_size_rx: None,
+            dir_size_cancel: None,
+            dir_size_paths: None,
+            dir_count_cache: std::collections::HashMap::new(),
+            dir_count_rx: None,
+            dir_count_cancel: None,
+            dir_count_paths: None,
+            pane_load_rx: Some(pane_load_rx),
+            operation_state_file,
+            octal_permissions: false,
+            last_auto_refresh: std::time::Instant::now(),
+            recent_files,
+            recent_files_file,
+            dialog_scroll: 0,
+            dir_summary_rx: None,
+            dir_summary_path: None,
+            toast: None,
+            message_history: Vec::new(),
+            quit_when_idle: false,
+        })
+    }
+
+
     fn draw(&mut self) -> Result<()> {
+        if self.current_dialog.is_none() {
+            self.dialog_scroll = 0;
+        }
+        let dialog_scroll = self.dialog_scroll;
+        let key_bar_labels = self.function_key_labels();
         let config = self.config.clone();
         let left_pane = self.left_pane.clone();
         let right_pane = self.right_pane.clone();
@@ -121,13 +346,17 @@ impl App {
         let current_dialog = self.current_dialog.clone();
         let mode = self.mode.clone();
         let viewer = self.viewer.clone();
-        
+        let toast = self.toast.as_ref().map(|(message, _)| message.clone());
+        let octal_permissions = self.octal_permissions;
+
         self.terminal.draw(|f| {
             match mode {
                 AppMode::Normal => {
-                    // Set the main background to blue (Norton Commander style)
-                    let main_block = Block::default()
-                        .style(Style::default().bg(Color::Blue));
+                    let styles = resolve_styles(&config);
+
+                    // Set the main background (Norton Commander blue, unless
+                    // UseColors is off)
+                    let main_block = Block::default().style(styles.background);
                     f.render_widget(main_block, f.size());
 
                     let chunks = Layout::default()
@@ -136,28 +365,30 @@ impl App {
                             Constraint::Length(1), // Title bar
                             Constraint::Min(1),    // Main content
                             Constraint::Length(1), // Status bar
+                            Constraint::Length(1), // Function key bar
                         ])
                         .split(f.size());
 
-                    // Title bar with blue background and cyan text
+                    // Title bar in the status bar colors
                     let title = Paragraph::new("Geek Commander")
-                        .style(Style::default().fg(Color::Cyan).bg(Color::Blue))
+                        .style(styles.status_bar)
                         .alignment(Alignment::Center);
                     f.render_widget(title, chunks[0]);
 
-                    // Main content area (dual panes)
+                    // Main content area (dual panes, split per config)
+                    let split = config.panels.split;
                     let main_chunks = Layout::default()
                         .direction(Direction::Horizontal)
-                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .constraints([Constraint::Percentage(split), Constraint::Percentage(100 - split)])
                         .split(chunks[1]);
 
                     // Left pane
-                    render_pane(f, main_chunks[0], &left_pane, active_pane == 0, &config);
-                    
-                    // Right pane  
-                    render_pane(f, main_chunks[1], &right_pane, active_pane == 1, &config);
+                    render_pane(f, main_chunks[0], &left_pane, active_pane == 0, &config, octal_permissions);
+
+                    // Right pane
+                    render_pane(f, main_chunks[1], &right_pane, active_pane == 1, &config, octal_permissions);
 
-                    // Status bar with blue background and cyan text
+                    // Status bar in the configured colors
                     let left_path = platform::path_to_display_string(&left_pane.current_path);
                     let right_path = platform::path_to_display_string(&right_pane.current_path);
                     let free_space = match platform::get_free_disk_space(&left_pane.current_path) {
@@ -165,24 +396,37 @@ impl App {
                         Err(_) => "Unknown".to_string(),
                     };
                     
-                    let status_text = format!(
-                        "Left: {} | Right: {} | Free: {} | F1 Help | F5 Copy | F6 Move | F7 NewDir | F8 Delete | F10 Exit",
-                        left_path, right_path, free_space
-                    );
-                    
+                    // Shorten deep paths so the free-space figure stays visible
+                    let path_budget =
+                        ((chunks[2].width as usize).saturating_sub(30) / 2).max(16);
+                    // An active toast borrows the status line until it expires
+                    let status_text = toast.clone().unwrap_or_else(|| format!(
+                        "Left: {} | Right: {} | Free: {}",
+                        platform::middle_truncate(&left_path, path_budget),
+                        platform::middle_truncate(&right_path, path_budget),
+                        free_space
+                    ));
+
                     let status = Paragraph::new(status_text)
-                        .style(Style::default().bg(Color::Blue).fg(Color::Cyan))
+                        .style(styles.status_bar)
                         .alignment(Alignment::Left);
                     f.render_widget(status, chunks[2]);
 
+                    render_function_key_bar(f, chunks[3], &key_bar_labels, &config);
+
                     // Render dialog if present
                     if let Some(ref dialog) = current_dialog {
-                        render_dialog(f, dialog, &config);
+                        render_dialog(f, dialog, dialog_scroll, &config);
                     }
                 },
                 AppMode::Viewer => {
                     if let Some(ref viewer) = viewer {
-                        viewer.render(f, f.size());
+                        let chunks = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([Constraint::Min(1), Constraint::Length(1)])
+                            .split(f.size());
+                        viewer.render(f, chunks[0]);
+                        render_function_key_bar(f, chunks[1], &key_bar_labels, &config);
                     }
                 },
             }
@@ -194,8 +438,8 @@ impl App {
         match self.mode {
             AppMode::Viewer => {
                 if let Some(ref mut viewer) = self.viewer {
-                    let visible_lines = self.terminal.size()?.height as usize - 3; // Account for title and status
-                    if !viewer.handle_key(key, visible_lines) {
+                    let visible_lines = self.terminal.size()?.height as usize - 4; // Title, status and key bar
+                    if !viewer.handle_key(key, modifiers, visible_lines) {
                         self.mode = AppMode::Normal;
                         self.viewer = None;
                     }
@@ -210,12 +454,97 @@ impl App {
 
                 // Calculate pane height for scrolling
                 let terminal_size = self.terminal.size()?;
-                let pane_height = terminal_size.height.saturating_sub(3) as usize; // Title + status bar
+                let pane_height = terminal_size.height.saturating_sub(4) as usize; // Title, status and key bars
 
                 // Handle core navigation keys directly first (before keybindings)
                 match key {
-                    KeyCode::Tab => {
-                        self.active_pane = if self.active_pane == 0 { 1 } else { 0 };
+                    KeyCode::F(1) if modifiers.contains(KeyModifiers::ALT) => {
+                        self.current_dialog = Some(DialogType::DriveSelect {
+                            drives: platform::list_drives(),
+                            selected: 0,
+                        });
+                        return Ok(());
+                    },
+                    KeyCode::Char('m') | KeyCode::Char('M') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.show_message_history();
+                        return Ok(());
+                    },
+                    KeyCode::Char('r') | KeyCode::Char('R') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.show_recent_files();
+                        return Ok(());
+                    },
+                    KeyCode::Char('f') | KeyCode::Char('F') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.toggle_flat_view()?;
+                        return Ok(());
+                    },
+                    KeyCode::Char('g') | KeyCode::Char('G') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.current_dialog = Some(DialogType::Input {
+                            prompt: "Select files changed since git ref (flat view catches subdirectories):".to_string(),
+                            input: "HEAD".to_string(),
+                            action: InputAction::SelectGitChanged,
+                        });
+                        return Ok(());
+                    },
+                    KeyCode::Char('c') | KeyCode::Char('C') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.handle_jump_canonical()?;
+                        return Ok(());
+                    },
+                    KeyCode::Char('x') | KeyCode::Char('X') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.octal_permissions = !self.octal_permissions;
+                        if self.config.general.show_permissions {
+                            self.show_toast(if self.octal_permissions {
+                                "Permissions shown in octal".to_string()
+                            } else {
+                                "Permissions shown symbolically".to_string()
+                            });
+                        }
+                        return Ok(());
+                    },
+                    KeyCode::Char('p') | KeyCode::Char('P') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.current_dialog = Some(DialogType::Input {
+                            prompt: "Export panel report to (relative paths go to the active pane):".to_string(),
+                            input: "panel-report.txt".to_string(),
+                            action: InputAction::ExportPanelReport,
+                        });
+                        return Ok(());
+                    },
+                    KeyCode::Char('o') | KeyCode::Char('O') if modifiers.contains(KeyModifiers::ALT) => {
+                        self.current_dialog = Some(DialogType::Input {
+                            prompt: "Run command (%l/%r pane paths, %L/%R selections, %f current file):".to_string(),
+                            input: String::new(),
+                            action: InputAction::RunCommand,
+                        });
+                        return Ok(());
+                    },
+                    KeyCode::F(9) => {
+                        self.open_context_menu_at_cursor()?;
+                        return Ok(());
+                    },
+                    KeyCode::Left if modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.adjust_split(-5);
+                        return Ok(());
+                    },
+                    KeyCode::Right if modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.adjust_split(5);
+                        return Ok(());
+                    },
+                    KeyCode::Left if self.config.general.navigation_style == NavigationStyle::Arrows => {
+                        // Focus the left pane, or go up when already there
+                        if self.active_pane == 1 {
+                            self.active_pane = 0;
+                        } else {
+                            self.handle_parent_directory()?;
+                        }
+                        return Ok(());
+                    },
+                    KeyCode::Right if self.config.general.navigation_style == NavigationStyle::Arrows => {
+                        // Focus the right pane, or enter the directory when
+                        // already there
+                        if self.active_pane == 0 {
+                            self.active_pane = 1;
+                        } else {
+                            self.handle_enter()?;
+                        }
                         return Ok(());
                     },
                     KeyCode::Up => {
@@ -227,7 +556,12 @@ impl App {
                         return Ok(());
                     },
                     KeyCode::Enter => {
-                        self.handle_enter()?;
+                        // Alt+Enter opens with the OS-associated application
+                        if modifiers.contains(KeyModifiers::ALT) {
+                            self.handle_open_with()?;
+                        } else {
+                            self.handle_enter()?;
+                        }
                         return Ok(());
                     },
                     KeyCode::Backspace => {
@@ -238,10 +572,16 @@ impl App {
                 }
 
                 // Check for configured keybindings
-                if self.config.keybindings.help.matches(key, modifiers) {
+                if self.config.keybindings.switch_pane.matches(key, modifiers) {
+                    self.active_pane = if self.active_pane == 0 { 1 } else { 0 };
+                } else if self.config.keybindings.help.matches(key, modifiers) {
                     self.current_dialog = Some(DialogType::Help);
                 } else if self.config.keybindings.quit.matches(key, modifiers) {
-                    self.should_quit = true;
+                    if self.active_operation.is_some() && self.config.confirmation.exit_with_jobs {
+                        self.confirm_quit_with_jobs();
+                    } else {
+                        self.should_quit = true;
+                    }
                 } else if self.config.keybindings.copy.matches(key, modifiers) {
                     self.handle_copy()?;
                 } else if self.config.keybindings.move_files.matches(key, modifiers) {
@@ -264,6 +604,20 @@ impl App {
                     self.handle_wildcard_select()?;
                 } else if self.config.keybindings.reload.matches(key, modifiers) {
                     self.handle_reload_config()?;
+                } else if self.config.keybindings.clipboard_copy.matches(key, modifiers) {
+                    self.handle_clipboard_collect(ClipboardMode::Copy);
+                } else if self.config.keybindings.clipboard_cut.matches(key, modifiers) {
+                    self.handle_clipboard_collect(ClipboardMode::Cut);
+                } else if self.config.keybindings.clipboard_paste.matches(key, modifiers) {
+                    self.handle_clipboard_paste()?;
+                } else if self.config.keybindings.info.matches(key, modifiers) {
+                    self.handle_info()?;
+                } else if self.config.keybindings.hotpath.matches(key, modifiers) {
+                    self.handle_hotpath();
+                } else if self.config.keybindings.compare.matches(key, modifiers) {
+                    self.current_dialog = Some(DialogType::CompareModeSelect { selected: 0 });
+                } else if self.config.keybindings.prev_dir.matches(key, modifiers) {
+                    self.get_active_pane_mut().toggle_previous_directory()?;
                 } else {
                     // Handle remaining navigation keys
                     match key {
@@ -288,6 +642,22 @@ impl App {
     }
 
     fn handle_dialog_key(&mut self, key: KeyCode, _modifiers: KeyModifiers, dialog: DialogType) -> Result<()> {
+        // PageUp/PageDown scroll long dialog content instead of acting on
+        // the dialog itself
+        match key {
+            KeyCode::PageUp => {
+                self.dialog_scroll = self.dialog_scroll.saturating_sub(1);
+                self.current_dialog = Some(dialog);
+                return Ok(());
+            },
+            KeyCode::PageDown => {
+                self.dialog_scroll = self.dialog_scroll.saturating_add(1);
+                self.current_dialog = Some(dialog);
+                return Ok(());
+            },
+            _ => {}
+        }
+
         match dialog {
             DialogType::Help => {
                 // Any key closes help
@@ -297,6 +667,52 @@ impl App {
                 // Any key closes error dialog
                 self.current_dialog = None;
             },
+            DialogType::QuitWithJobs { message } => {
+                match key {
+                    KeyCode::Char('w') | KeyCode::Char('W') => {
+                        self.quit_when_idle = true;
+                        self.current_dialog = None;
+                        self.show_toast("Quitting once the running operation finishes".to_string());
+                    },
+                    KeyCode::Char('c') | KeyCode::Char('C') => {
+                        if let Some(shared) = &self.active_operation {
+                            shared.lock().unwrap().cancelled = true;
+                        }
+                        self.quit_when_idle = true;
+                        self.current_dialog = None;
+                    },
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                        self.current_dialog = None;
+                    },
+                    _ => {
+                        self.current_dialog = Some(DialogType::QuitWithJobs { message });
+                    }
+                }
+            },
+            DialogType::Info { .. } => {
+                // Any key closes info dialog
+                self.current_dialog = None;
+            },
+            DialogType::ContextMenu { mut selected, x, y } => {
+                match key {
+                    KeyCode::Up => {
+                        selected = if selected == 0 { CONTEXT_MENU_ITEMS.len() - 1 } else { selected - 1 };
+                        self.current_dialog = Some(DialogType::ContextMenu { selected, x, y });
+                    },
+                    KeyCode::Down => {
+                        selected = (selected + 1) % CONTEXT_MENU_ITEMS.len();
+                        self.current_dialog = Some(DialogType::ContextMenu { selected, x, y });
+                    },
+                    KeyCode::Enter => {
+                        self.current_dialog = None;
+                        self.execute_context_menu_item(selected)?;
+                    },
+                    KeyCode::Esc => {
+                        self.current_dialog = None;
+                    },
+                    _ => {}
+                }
+            },
             DialogType::Confirm { action, .. } => {
                 match key {
                     KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -305,6 +721,13 @@ impl App {
                     },
                     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                         self.current_dialog = None;
+                        // A declined resume must not be offered again
+                        if action == ConfirmAction::ResumeOperation {
+                            self.pending_operation = None;
+                            clear_operation_state(&self.operation_state_file);
+                        } else if action == ConfirmAction::ProceedChecks {
+                            self.pending_operation = None;
+                        }
                     },
                     _ => {}
                 }
@@ -329,11 +752,153 @@ impl App {
                     _ => {}
                 }
             },
+            DialogType::DriveSelect { drives, mut selected } => {
+                match key {
+                    KeyCode::Up => {
+                        selected = selected.saturating_sub(1);
+                        self.current_dialog = Some(DialogType::DriveSelect { drives, selected });
+                    },
+                    KeyCode::Down => {
+                        if selected + 1 < drives.len() {
+                            selected += 1;
+                        }
+                        self.current_dialog = Some(DialogType::DriveSelect { drives, selected });
+                    },
+                    KeyCode::Enter => {
+                        self.current_dialog = None;
+                        if let Some(mount_point) = drives.get(selected).and_then(|d| d.mount_point.clone()) {
+                            self.get_active_pane_mut().enter_directory(mount_point)?;
+                        }
+                    },
+                    KeyCode::Char('m') | KeyCode::Char('M') => {
+                        if let Some(drive) = drives.get(selected) {
+                            match platform::mount_drive(&drive.device) {
+                                Ok(_) => {
+                                    self.current_dialog = Some(DialogType::DriveSelect {
+                                        drives: platform::list_drives(),
+                                        selected,
+                                    });
+                                },
+                                Err(e) => self.show_error(format!("Mount failed: {}", e)),
+                            }
+                        }
+                    },
+                    KeyCode::Char('u') | KeyCode::Char('U') => {
+                        if let Some(drive) = drives.get(selected) {
+                            match platform::unmount_drive(&drive.device) {
+                                Ok(_) => {
+                                    self.recover_unmounted_panes()?;
+                                    self.current_dialog = Some(DialogType::DriveSelect {
+                                        drives: platform::list_drives(),
+                                        selected: 0,
+                                    });
+                                },
+                                Err(e) => self.show_error(format!("Unmount failed: {}", e)),
+                            }
+                        }
+                    },
+                    KeyCode::Char('e') | KeyCode::Char('E') => {
+                        if let Some(drive) = drives.get(selected) {
+                            match platform::eject_drive(&drive.device) {
+                                Ok(_) => {
+                                    self.recover_unmounted_panes()?;
+                                    self.current_dialog = Some(DialogType::DriveSelect {
+                                        drives: platform::list_drives(),
+                                        selected: 0,
+                                    });
+                                },
+                                Err(e) => self.show_error(format!("Eject failed: {}", e)),
+                            }
+                        }
+                    },
+                    KeyCode::Esc => {
+                        self.current_dialog = None;
+                    },
+                    _ => {}
+                }
+            },
             DialogType::Progress { .. } => {
-                // Progress dialogs are typically non-interactive
-                // Could add cancel functionality here
+                // Esc asks the worker thread to stop; the dialog closes once
+                // it has actually wound down
                 if key == KeyCode::Esc {
-                    self.current_dialog = None;
+                    if let Some(shared) = &self.active_operation {
+                        shared.lock().unwrap().cancelled = true;
+                    } else {
+                        self.current_dialog = None;
+                    }
+                }
+            },
+            DialogType::Scanning { .. } => {
+                // Handled by the modal pre-scan loop, not the main event loop
+            },
+            DialogType::HotPath { ancestors, mut selected } => {
+                match key {
+                    KeyCode::Up => {
+                        selected = selected.saturating_sub(1);
+                        self.current_dialog = Some(DialogType::HotPath { ancestors, selected });
+                    },
+                    KeyCode::Down => {
+                        if selected + 1 < ancestors.len() {
+                            selected += 1;
+                        }
+                        self.current_dialog = Some(DialogType::HotPath { ancestors, selected });
+                    },
+                    KeyCode::Enter => {
+                        self.current_dialog = None;
+                        if let Some(ancestor) = ancestors.get(selected) {
+                            self.get_active_pane_mut().enter_directory(ancestor.clone())?;
+                        }
+                    },
+                    KeyCode::Esc => {
+                        self.current_dialog = None;
+                    },
+                    _ => {}
+                }
+            },
+            DialogType::RecentFiles { files, mut selected } => {
+                match key {
+                    KeyCode::Up => {
+                        selected = selected.saturating_sub(1);
+                        self.current_dialog = Some(DialogType::RecentFiles { files, selected });
+                    },
+                    KeyCode::Down => {
+                        if selected + 1 < files.len() {
+                            selected += 1;
+                        }
+                        self.current_dialog = Some(DialogType::RecentFiles { files, selected });
+                    },
+                    KeyCode::Enter => {
+                        self.current_dialog = None;
+                        if let Some(file) = files.get(selected) {
+                            self.jump_to_file(file.clone())?;
+                        }
+                    },
+                    KeyCode::Esc => {
+                        self.current_dialog = None;
+                    },
+                    _ => {}
+                }
+            },
+            DialogType::CompareModeSelect { mut selected } => {
+                match key {
+                    KeyCode::Up => {
+                        selected = selected.saturating_sub(1);
+                        self.current_dialog = Some(DialogType::CompareModeSelect { selected });
+                    },
+                    KeyCode::Down => {
+                        if selected + 1 < COMPARE_MODES.len() {
+                            selected += 1;
+                        }
+                        self.current_dialog = Some(DialogType::CompareModeSelect { selected });
+                    },
+                    KeyCode::Enter => {
+                        self.current_dialog = None;
+                        self.perform_compare(COMPARE_MODES[selected]);
+                    },
+                    KeyCode::Esc => {
+                        self.current_dialog = None;
+                    },
+                    _ => {}
                 }
             },
         }
@@ -363,15 +928,25 @@ impl App {
                 let new_path = if entry.name == ".." {
                     pane.current_path.parent().unwrap_or(&pane.current_path).to_path_buf()
                 } else {
-                    pane.current_path.join(&entry.name)
+                    // Use the raw path so names with invalid UTF-8 still resolve
+                    entry.path.clone()
                 };
                 pane.enter_directory(new_path)?;
             } else if entry.is_archive {
                 // TODO: Implement archive navigation
                 self.show_error("Archive navigation not yet implemented".to_string());
             } else {
-                // Open file in viewer
-                self.handle_view()?;
+                // The action is configurable per extension; the viewer is
+                // the default
+                let extension = entry.path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                match self.config.enter_actions.get(&extension).copied().unwrap_or(EnterAction::View) {
+                    EnterAction::View => self.handle_view()?,
+                    EnterAction::Edit => self.handle_edit()?,
+                    EnterAction::Open => self.handle_open_with()?,
+                }
             }
         }
         Ok(())
@@ -393,35 +968,87 @@ impl App {
         let selected = self.get_active_pane_mut().get_selected_entries().len();
         let dest_path = self.get_inactive_pane().current_path.clone();
         
-        if selected == 0 {
-            if let Some(current) = current_entry {
-                if current.name != ".." {
-                    let message = format!("Copy '{}' to '{}'?", current.name, dest_path.display());
-                    self.current_dialog = Some(DialogType::Confirm {
-                        message,
-                        action: ConfirmAction::Copy,
-                    });
-                }
+        let what = if selected == 0 {
+            match current_entry {
+                Some(current) if current.name != ".." => format!("'{}'", current.name),
+                _ => return Ok(()),
             }
         } else {
-            let message = format!("Copy {} selected files to '{}'?", selected, dest_path.display());
-            self.current_dialog = Some(DialogType::Confirm {
-                message,
-                action: ConfirmAction::Copy,
-            });
-        }
+            format!("{} selected files", selected)
+        };
+
+        // Enter confirms the copy; exclude globs are optional (e.g. "*.o target/")
+        let size_line = self.transfer_size_line(&dest_path);
+        self.current_dialog = Some(DialogType::Input {
+            prompt: format!(
+                "Copy {} to '{}' — exclude patterns (optional):\n{}",
+                what,
+                dest_path.display(),
+                size_line
+            ),
+            input: String::new(),
+            action: InputAction::CopyExcludes,
+        });
         Ok(())
     }
 
+    /// One-line sanity check for the copy/move dialogs: the total size of
+    /// the chosen entries next to the free space on the destination volume.
+    /// Directory sizes come from the background size cache; a trailing `+`
+    /// marks directories that have not been measured yet.
+    fn transfer_size_line(&self, dest: &std::path::Path) -> String {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let selected = pane.get_selected_entries();
+        let entries: Vec<&FileEntry> = if selected.is_empty() {
+            pane.get_current_entry().filter(|e| e.name != "..").into_iter().collect()
+        } else {
+            selected
+        };
+
+        let mut total = 0u64;
+        let mut unmeasured_dirs = false;
+        for entry in entries {
+            if entry.is_dir {
+                match self.dir_size_cache.get(&entry.path) {
+                    Some(size) => total += size,
+                    None => unmeasured_dirs = true,
+                }
+            } else {
+                total += entry.size;
+            }
+        }
+
+        let size = if unmeasured_dirs {
+            format!("{}+", platform::format_file_size(total))
+        } else {
+            platform::format_file_size(total)
+        };
+        let free = match platform::get_free_disk_space(dest) {
+            Ok(space) => platform::format_file_size(space),
+            Err(_) => "unknown".to_string(),
+        };
+        format!("Size: {} | Free on destination: {}", size, free)
+    }
+
     fn handle_move(&mut self) -> Result<()> {
         let current_entry = self.get_active_pane_mut().get_current_entry().cloned();
         let selected = self.get_active_pane_mut().get_selected_entries().len();
         let dest_path = self.get_inactive_pane().current_path.clone();
-        
+
+        if !self.config.confirmation.moves {
+            return self.execute_confirm_action(ConfirmAction::Move);
+        }
+
+        let size_line = self.transfer_size_line(&dest_path);
         if selected == 0 {
             if let Some(current) = current_entry {
                 if current.name != ".." {
-                    let message = format!("Move '{}' to '{}'?", current.name, dest_path.display());
+                    let message = format!(
+                        "Move '{}' to '{}'?\n{}",
+                        current.name,
+                        dest_path.display(),
+                        size_line
+                    );
                     self.current_dialog = Some(DialogType::Confirm {
                         message,
                         action: ConfirmAction::Move,
@@ -429,7 +1056,12 @@ impl App {
                 }
             }
         } else {
-            let message = format!("Move {} selected files to '{}'?", selected, dest_path.display());
+            let message = format!(
+                "Move {} selected files to '{}'?\n{}",
+                selected,
+                dest_path.display(),
+                size_line
+            );
             self.current_dialog = Some(DialogType::Confirm {
                 message,
                 action: ConfirmAction::Move,
@@ -439,6 +1071,12 @@ impl App {
     }
 
     fn handle_delete(&mut self) -> Result<()> {
+        // The recursive non-empty-directory warning inside the Delete action
+        // still fires even with the first confirmation turned off
+        if !self.config.confirm_delete() {
+            return self.execute_confirm_action(ConfirmAction::Delete);
+        }
+
         let selected = self.get_active_pane_mut().get_selected_entries();
         if selected.is_empty() {
             if let Some(current) = self.get_active_pane_mut().get_current_entry() {
@@ -464,7 +1102,7 @@ impl App {
         if let Some(current) = self.get_active_pane_mut().get_current_entry() {
             if current.name != ".." {
                 self.current_dialog = Some(DialogType::Input {
-                    prompt: "Rename to:".to_string(),
+                    prompt: "Rename or move to:".to_string(),
                     input: current.name.clone(),
                     action: InputAction::Rename,
                 });
@@ -475,7 +1113,7 @@ impl App {
 
     fn handle_new_directory(&mut self) -> Result<()> {
         self.current_dialog = Some(DialogType::Input {
-            prompt: "Create directory:".to_string(),
+            prompt: "Create directory (a/b/c nests, ; separates several):".to_string(),
             input: String::new(),
             action: InputAction::NewDirectory,
         });
@@ -484,14 +1122,31 @@ impl App {
 
     fn handle_view(&mut self) -> Result<()> {
         if let Some(current) = self.get_active_pane_mut().get_current_entry() {
-            if !current.is_dir && current.name != ".." {
-                match FileViewer::new(&current.path) {
+            if current.is_dir && current.name != ".." {
+                // Summarize the tree in the background; the viewer opens
+                // once the scan delivers its result
+                let path = current.path.clone();
+                self.dir_summary_rx = Some(spawn_directory_summary(path.clone()));
+                self.dir_summary_path = Some(path);
+            } else if !current.is_dir && current.name != ".." {
+                let path = current.path.clone();
+                match FileViewer::new_with_limit(&path, self.viewer_size_limit(&path)) {
+                    Ok(viewer) if viewer.is_binary => {
+                        self.current_dialog = Some(DialogType::Confirm {
+                            message: "This is a binary file.\nView it as a hex dump?".to_string(),
+                            action: ConfirmAction::ViewAsHex(path),
+                        });
+                    },
                     Ok(viewer) => {
                         self.viewer = Some(viewer);
                         self.mode = AppMode::Viewer;
+                        self.record_recent_file(path);
                     },
                     Err(e) => {
-                        self.show_error(format!("Cannot view file: {}", e));
+                        self.current_dialog = Some(DialogType::Confirm {
+                            message: format!("Cannot view file: {}\nView it as a hex dump instead?", e),
+                            action: ConfirmAction::ViewAsHex(path),
+                        });
                     }
                 }
             }
@@ -502,10 +1157,12 @@ impl App {
     fn handle_edit(&mut self) -> Result<()> {
         if let Some(current) = self.get_active_pane_mut().get_current_entry() {
             if !current.is_dir && current.name != ".." {
-                match launch_external_editor(&current.path) {
+                let path = current.path.clone();
+                match launch_external_editor(&path) {
                     Ok(_) => {
                         // Refresh the pane after editing
                         self.get_active_pane_mut().refresh()?;
+                        self.record_recent_file(path);
                     },
                     Err(e) => {
                         self.show_error(format!("Cannot edit file: {}", e));
@@ -533,59 +1190,338 @@ impl App {
 
     fn handle_wildcard_select(&mut self) -> Result<()> {
         self.current_dialog = Some(DialogType::Input {
-            prompt: "Select files matching pattern:".to_string(),
+            prompt: "Select files matching pattern (@file reads names from a list):".to_string(),
             input: "*.".to_string(),
             action: InputAction::SelectByPattern,
         });
         Ok(())
     }
 
-    fn handle_reload_config(&mut self) -> Result<()> {
-        match crate::config::Config::load_or_create_default(None) {
-            Ok(config) => {
-                self.config = config;
-                // Could show a success message
+    /// Add the selection (or cursor entry) to the internal clipboard.
+    /// Repeated invocations with the same mode accumulate entries across
+    /// directories; switching between copy and cut restarts the clipboard.
+    fn handle_clipboard_collect(&mut self, mode: ClipboardMode) {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let selected = pane.get_selected_entries();
+        let mut paths: Vec<std::path::PathBuf> = if selected.is_empty() {
+            match pane.get_current_entry() {
+                Some(entry) if entry.name != ".." => vec![entry.path.clone()],
+                _ => return,
+            }
+        } else {
+            selected.iter().map(|e| e.path.clone()).collect()
+        };
+
+        match &mut self.clipboard {
+            Some((existing_mode, existing)) if *existing_mode == mode => {
+                paths.retain(|p| !existing.contains(p));
+                existing.append(&mut paths);
+            },
+            _ => {
+                self.clipboard = Some((mode, paths));
+            }
+        }
+
+        self.get_active_pane_mut().deselect_all();
+    }
+
+    fn handle_clipboard_paste(&mut self) -> Result<()> {
+        let (mode, paths) = match self.clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => return Ok(()),
+        };
+
+        let dest = if self.active_pane == 0 {
+            self.left_pane.current_path.clone()
+        } else {
+            self.right_pane.current_path.clone()
+        };
+
+        let operation = match mode {
+            ClipboardMode::Copy => crate::core::copy_paths(paths, &dest),
+            ClipboardMode::Cut => crate::core::move_paths(paths, &dest),
+        };
+        self.start_operation(operation)
+    }
+
+    /// Show a dialog with details of the entry under the cursor, including
+    /// other hard links of the file when it has more than one
+    fn handle_info(&mut self) -> Result<()> {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let entry = match pane.get_current_entry() {
+            Some(entry) if entry.name != ".." => entry.clone(),
+            _ => return Ok(()),
+        };
+        let scan_root = pane.current_path.clone();
+
+        let mut message = format!(
+            "Name: {}\nPath: {}\nSize: {}\nModified: {}\nPermissions: {}",
+            entry.name,
+            platform::path_to_display_string(&entry.path),
+            if entry.is_dir {
+                "<DIR>".to_string()
+            } else if platform::is_sparse_file(&entry.path) {
+                format!(
+                    "{} ({} allocated, sparse)",
+                    platform::format_file_size(entry.size),
+                    platform::format_file_size(platform::allocated_file_size(&entry.path).unwrap_or(0))
+                )
+            } else {
+                platform::format_file_size(entry.size)
             },
+            platform::format_file_time(entry.modified),
+            entry.permissions,
+        );
+
+        // A path that leads through a symlink also gets its resolved form
+        if let Ok(canonical) = entry.path.canonicalize() {
+            if canonical != entry.path {
+                message.push_str(&format!(
+                    "\nPhysical path: {}",
+                    platform::path_to_display_string(&canonical)
+                ));
+            }
+        }
+
+        if let Ok(metadata) = std::fs::metadata(&entry.path) {
+            if let Some((_, inode)) = platform::get_file_id(&metadata) {
+                message.push_str(&format!("\nInode: {}\nHard links: {}", inode, entry.nlink));
+            }
+        }
+
+        if !entry.is_dir {
+            let streams = platform::list_alternate_data_streams(&entry.path);
+            if !streams.is_empty() {
+                message.push_str("\n\nAlternate data streams:");
+                for (name, size) in streams {
+                    message.push_str(&format!("\n  :{} ({})", name, platform::format_file_size(size)));
+                }
+            }
+        }
+
+        // ZIP archives get their comment and per-entry metadata
+        if is_zip_archive(&entry.path) {
+            if let Ok(comment) = crate::archive::zip_comment(&entry.path) {
+                if comment.is_empty() {
+                    message.push_str("\n\nArchive comment: (none)");
+                } else {
+                    message.push_str(&format!("\n\nArchive comment: {}", comment));
+                }
+            }
+            if let Ok(infos) = crate::archive::zip_entry_infos(&entry.path) {
+                // Keep huge archives from flooding the dialog
+                const MAX_LISTED: usize = 50;
+                message.push_str(&format!("\n\n{} archived file(s):", infos.len()));
+                for info in infos.iter().take(MAX_LISTED) {
+                    let ratio = (info.compressed_size * 100)
+                        .checked_div(info.size)
+                        .map_or(0, |used| 100 - used.min(100));
+                    message.push_str(&format!(
+                        "\n  {}  {} ({}, {}% saved, CRC {:08x})",
+                        info.name,
+                        platform::format_file_size(info.size),
+                        info.method,
+                        ratio,
+                        info.crc32,
+                    ));
+                }
+                if infos.len() > MAX_LISTED {
+                    message.push_str("\n  ... (listing truncated)");
+                }
+            }
+        }
+
+        if !entry.is_dir && entry.nlink > 1 {
+            match crate::core::find_hardlinks(&scan_root, &entry.path, 10) {
+                Ok(links) if !links.is_empty() => {
+                    message.push_str("\n\nOther hard links (in this directory tree):");
+                    for link in links {
+                        message.push_str(&format!("\n  {}", platform::path_to_display_string(&link)));
+                    }
+                },
+                Ok(_) => {
+                    message.push_str("\n\nNo other hard links found in this directory tree");
+                },
+                Err(e) => {
+                    message.push_str(&format!("\n\nHard link scan failed: {}", e));
+                },
+            }
+        }
+
+        self.current_dialog = Some(DialogType::Info {
+            title: "File Info".to_string(),
+            message,
+        });
+        Ok(())
+    }
+
+    /// Alt+C: jump to the canonical location of the current directory, for
+    /// panes that arrived here through a symlinked directory
+    fn handle_jump_canonical(&mut self) -> Result<()> {
+        let pane = self.get_active_pane_mut();
+        let canonical = match pane.current_path.canonicalize() {
+            Ok(canonical) => canonical,
             Err(e) => {
-                self.show_error(format!("Failed to reload config: {}", e));
+                self.show_error(format!("Cannot resolve path: {}", e));
+                return Ok(());
+            }
+        };
+        if canonical == pane.current_path {
+            self.show_toast("Already at the canonical path".to_string());
+            return Ok(());
+        }
+        pane.enter_directory(canonical.clone())?;
+        self.show_toast(format!("Jumped to {}", platform::path_to_display_string(&canonical)));
+        Ok(())
+    }
+
+    /// Offer the ancestors of the current directory for a quick jump up the
+    /// tree, instead of walking up one Backspace at a time
+    fn handle_hotpath(&mut self) {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let ancestors: Vec<std::path::PathBuf> = pane.current_path
+            .ancestors()
+            .skip(1)
+            .map(|p| p.to_path_buf())
+            .collect();
+
+        if !ancestors.is_empty() {
+            self.current_dialog = Some(DialogType::HotPath { ancestors, selected: 0 });
+        }
+    }
+
+    /// Periodically re-read pane directories whose mtime changed, so
+    /// listings on network mounts without change notification don't go
+    /// stale. Gated on `AutoRefreshSeconds` and paused during operations,
+    /// whose own refreshes already keep the panes current.
+    fn maybe_auto_refresh(&mut self) {
+        let interval = self.config.general.auto_refresh_secs;
+        if interval == 0 || self.active_operation.is_some() {
+            return;
+        }
+        if self.last_auto_refresh.elapsed() < std::time::Duration::from_secs(interval) {
+            return;
+        }
+        self.last_auto_refresh = std::time::Instant::now();
+        for pane in [&mut self.left_pane, &mut self.right_pane] {
+            // The startup load owns panes that are still spinning
+            if pane.loading {
+                continue;
+            }
+            if let Err(e) = pane.refresh_if_changed() {
+                log::warn!("Auto-refresh failed: {}", e);
             }
         }
+    }
+
+    /// Alt+F: switch the active pane between the normal listing and the
+    /// recursive flat view, e.g. for mass-selecting by pattern across
+    /// subdirectories
+    fn toggle_flat_view(&mut self) -> Result<()> {
+        let pane = self.get_active_pane_mut();
+        if pane.archive_context.is_some() {
+            return Ok(());
+        }
+        pane.flat_view = !pane.flat_view;
+        let enabled = pane.flat_view;
+        pane.deselect_all();
+        pane.refresh()?;
+        self.show_toast(if enabled {
+            "Flat view on".to_string()
+        } else {
+            "Flat view off".to_string()
+        });
+        Ok(())
+    }
+
+    /// Offer the recently viewed/edited/opened files for quick reopening
+    fn show_recent_files(&mut self) {
+        // Prune entries whose files have since been deleted or moved
+        self.recent_files.retain(|file| file.exists());
+        if self.recent_files.is_empty() {
+            self.show_toast("No recent files".to_string());
+            return;
+        }
+        self.current_dialog = Some(DialogType::RecentFiles {
+            files: self.recent_files.clone(),
+            selected: 0,
+        });
+    }
+
+    /// Record a file in the recent-files history, newest first, and persist
+    /// the list so it survives restarts
+    fn record_recent_file(&mut self, path: std::path::PathBuf) {
+        self.recent_files.retain(|file| file != &path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(RECENT_FILES_LIMIT);
+        if let Err(e) = save_recent_files(&self.recent_files_file, &self.recent_files) {
+            log::warn!("Failed to save recent files: {}", e);
+        }
+    }
+
+    /// Jump the active pane to the file's directory and put the cursor on it
+    fn jump_to_file(&mut self, file: std::path::PathBuf) -> Result<()> {
+        let parent = match file.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => return Ok(()),
+        };
+        let name = file.file_name().map(|n| n.to_string_lossy().to_string());
+        let pane = self.get_active_pane_mut();
+        pane.enter_directory(parent)?;
+        if let Some(name) = name {
+            pane.focus_entry(&name);
+        }
+        Ok(())
+    }
+
+    /// Compare the two pane directories with the chosen tolerance mode and
+    /// show the resulting report
+    fn perform_compare(&mut self, mode: crate::sync::CompareMode) {
+        let left = self.left_pane.current_path.clone();
+        let right = self.right_pane.current_path.clone();
+
+        match crate::sync::compare_directories(&left, &right, mode) {
+            Ok(report) => {
+                self.current_dialog = Some(DialogType::Info {
+                    title: "Compare report".to_string(),
+                    message: report.to_text(),
+                });
+            },
+            Err(e) => self.show_error(format!("Compare failed: {}", e)),
+        }
+    }
+
+    fn handle_reload_config(&mut self) -> Result<()> {
+        let (config, problems) = crate::config::Config::load_with_report(None, self.config.portable);
+        self.config = config;
+        self.left_pane.dirs_placement = self.config.general.dirs_placement;
+        self.right_pane.dirs_placement = self.config.general.dirs_placement;
+        self.left_pane.case_sensitivity = self.config.general.case_sensitivity;
+        self.right_pane.case_sensitivity = self.config.general.case_sensitivity;
+        self.left_pane.refresh()?;
+        self.right_pane.refresh()?;
+        if !problems.is_empty() {
+            self.show_config_problems(&problems);
+        } else {
+            self.show_toast("Configuration reloaded".to_string());
+        }
         Ok(())
     }
 
+    /// Show the collected configuration problems in a single report dialog
+    pub fn show_config_problems(&mut self, problems: &[String]) {
+        self.current_dialog = Some(DialogType::Info {
+            title: "Configuration problems".to_string(),
+            message: problems.join("\n"),
+        });
+    }
+
     fn execute_confirm_action(&mut self, action: ConfirmAction) -> Result<()> {
         let dest = self.get_inactive_pane().current_path.clone();
         
         match action {
             ConfirmAction::Copy => {
-                let selected = self.get_active_pane_mut().get_selected_entries();
-                let sources = if selected.is_empty() {
-                    if let Some(current) = self.get_active_pane_mut().get_current_entry() {
-                        vec![current]
-                    } else {
-                        return Ok(());
-                    }
-                } else {
-                    selected
-                };
-                
-                match copy_files(&sources, &dest) {
-                    Ok(mut operation) => {
-                        // Execute the operation (simplified for now)
-                        if let Err(e) = execute_operation(&mut operation) {
-                            self.show_error(format!("Copy failed: {}", e));
-                        } else {
-                            // Refresh both panes
-                            self.left_pane.refresh()?;
-                            self.right_pane.refresh()?;
-                            // Clear selections
-                            self.get_active_pane_mut().deselect_all();
-                        }
-                    },
-                    Err(e) => {
-                        self.show_error(format!("Copy failed: {}", e));
-                    }
-                }
+                self.perform_copy(Vec::new())?;
             },
             ConfirmAction::Move => {
                 let selected = self.get_active_pane_mut().get_selected_entries();
@@ -600,14 +1536,8 @@ impl App {
                 };
                 
                 match move_files(&sources, &dest) {
-                    Ok(mut operation) => {
-                        if let Err(e) = execute_operation(&mut operation) {
-                            self.show_error(format!("Move failed: {}", e));
-                        } else {
-                            self.left_pane.refresh()?;
-                            self.right_pane.refresh()?;
-                            self.get_active_pane_mut().deselect_all();
-                        }
+                    Ok(operation) => {
+                        self.start_operation(operation)?;
                     },
                     Err(e) => {
                         self.show_error(format!("Move failed: {}", e));
@@ -615,44 +1545,103 @@ impl App {
                 }
             },
             ConfirmAction::Delete => {
-                let selected = self.get_active_pane_mut().get_selected_entries();
-                let sources = if selected.is_empty() {
-                    if let Some(current) = self.get_active_pane_mut().get_current_entry() {
-                        vec![current]
+                // Non-empty directories get a second, stronger confirmation with
+                // recursive stats computed in the background.
+                let non_empty_dirs: Vec<std::path::PathBuf> = self.collect_delete_sources()
+                    .iter()
+                    .filter(|e| e.is_dir && e.name != ".." && !is_directory_empty(&e.path))
+                    .map(|e| e.path.clone())
+                    .collect();
+
+                if !non_empty_dirs.is_empty() {
+                    let (tx, rx) = std::sync::mpsc::channel();
+                    let paths = non_empty_dirs.clone();
+                    std::thread::spawn(move || {
+                        if let Ok(stats) = directory_stats(&paths) {
+                            let _ = tx.send(stats);
+                        }
+                    });
+                    self.pending_delete_stats = Some(rx);
+
+                    let message = if non_empty_dirs.len() == 1 {
+                        format!(
+                            "'{}' is a NON-EMPTY directory.\nContents: calculating...\n\nReally delete it and everything inside?",
+                            non_empty_dirs[0].display()
+                        )
                     } else {
-                        return Ok(());
-                    }
+                        format!(
+                            "{} of the targets are NON-EMPTY directories.\nContents: calculating...\n\nReally delete them and everything inside?",
+                            non_empty_dirs.len()
+                        )
+                    };
+                    self.current_dialog = Some(DialogType::Confirm {
+                        message,
+                        action: ConfirmAction::DeleteRecursive,
+                    });
                 } else {
-                    selected
-                };
-                
-                match delete_files(&sources) {
-                    Ok(mut operation) => {
-                        if let Err(e) = execute_operation(&mut operation) {
-                            self.show_error(format!("Delete failed: {}", e));
-                        } else {
-                            self.get_active_pane_mut().refresh()?;
-                            self.get_active_pane_mut().deselect_all();
-                        }
-                    },
-                    Err(e) => {
-                        self.show_error(format!("Delete failed: {}", e));
-                    }
+                    self.perform_delete()?;
                 }
             },
+            ConfirmAction::DeleteRecursive => {
+                self.pending_delete_stats = None;
+                self.perform_delete()?;
+            },
             ConfirmAction::Overwrite => {
                 // Handle file overwrite confirmation
             },
+            ConfirmAction::ProceedLowSpace => {
+                if let Some(operation) = self.pending_operation.take() {
+                    self.launch_operation(operation)?;
+                }
+            },
+            ConfirmAction::ProceedChecks => {
+                if let Some(operation) = self.pending_operation.take() {
+                    self.check_space_and_launch(operation)?;
+                }
+            },
+            ConfirmAction::ResumeOperation => {
+                if let Some(operation) = self.pending_operation.take() {
+                    self.start_operation(operation)?;
+                }
+            },
+            ConfirmAction::ViewAsHex(path) => {
+                match FileViewer::new_hex(&path) {
+                    Ok(viewer) => {
+                        self.viewer = Some(viewer);
+                        self.mode = AppMode::Viewer;
+                        self.record_recent_file(path);
+                    },
+                    Err(e) => {
+                        self.show_error(format!("Cannot view file: {}", e));
+                    }
+                }
+            },
         }
         Ok(())
     }
 
+    /// The viewer size cap for this file: its extension's `[ViewerLimits]`
+    /// entry, the configured `default`, or the built-in 50MB cap
+    fn viewer_size_limit(&self, path: &std::path::Path) -> u64 {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        self.config
+            .viewer_limits
+            .get(&extension)
+            .or_else(|| self.config.viewer_limits.get("default"))
+            .copied()
+            .unwrap_or(crate::viewer::MAX_FILE_SIZE_FOR_VIEWING)
+    }
+
     fn execute_input_action(&mut self, action: InputAction, input: &str) -> Result<()> {
         match action {
             InputAction::NewDirectory => {
                 if !input.trim().is_empty() {
+                    let new_dir_mode = self.config.general.new_dir_mode;
                     let current_path = &self.get_active_pane_mut().current_path;
-                    match create_directory(current_path, input.trim()) {
+                    match create_directories(current_path, input, new_dir_mode) {
                         Ok(_) => {
                             self.get_active_pane_mut().refresh()?;
                         },
@@ -676,12 +1665,127 @@ impl App {
                     }
                 }
             },
+            InputAction::CopyExcludes => {
+                let exclude_patterns: Vec<String> = input
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .collect();
+                self.perform_copy(exclude_patterns)?;
+            },
+            InputAction::ExportPanelReport => {
+                let trimmed = input.trim();
+                if !trimmed.is_empty() {
+                    let mut target = std::path::PathBuf::from(trimmed);
+                    if target.is_relative() {
+                        target = self.get_active_pane_mut().current_path.join(target);
+                    }
+                    let report = build_panel_report(&self.left_pane, &self.right_pane);
+                    match platform::atomic_write(&target, &report) {
+                        Ok(()) => {
+                            // The report may have landed in a visible pane
+                            self.get_active_pane_mut().refresh()?;
+                            self.show_toast(format!("Report written to {}", target.display()));
+                        },
+                        Err(e) => self.show_error(format!("Failed to write report: {}", e)),
+                    }
+                }
+            },
+            InputAction::SelectGitChanged => {
+                let reference = if input.trim().is_empty() { "HEAD" } else { input.trim() };
+                let dir = self.get_active_pane_mut().current_path.clone();
+                match git_changed_files(&dir, reference) {
+                    Ok(files) => {
+                        // Full paths, so entries match both in the normal
+                        // listing and in the flat view
+                        let names: Vec<String> = files
+                            .iter()
+                            .map(|file| dir.join(file).to_string_lossy().to_string())
+                            .collect();
+                        let count = self.get_active_pane_mut().select_named(&names);
+                        if count == 0 {
+                            self.show_toast(format!("No files changed since {} in this listing", reference));
+                        } else {
+                            self.show_toast(format!("Selected {} file(s) changed since {}", count, reference));
+                        }
+                    },
+                    Err(e) => {
+                        self.show_error(format!("Cannot list changed files: {}", e));
+                    }
+                }
+            },
+            InputAction::EditZipComment => {
+                let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+                if let Some(entry) = pane.get_current_entry() {
+                    let path = entry.path.clone();
+                    match crate::archive::set_zip_comment(&path, input) {
+                        Ok(()) => self.show_toast("Archive comment updated".to_string()),
+                        Err(e) => self.show_error(format!("Failed to update comment: {}", e)),
+                    }
+                }
+            },
+            InputAction::RunCommand => {
+                if input.trim().is_empty() {
+                    return Ok(());
+                }
+                let command = expand_command_variables(input, &self.left_pane, &self.right_pane, self.active_pane);
+                let dir = self.get_active_pane_mut().current_path.clone();
+                match platform::run_shell_command(&command, &dir) {
+                    Ok(output) => {
+                        let mut message = format!("$ {}", command);
+                        let stdout = String::from_utf8_lossy(&output.stdout);
+                        if !stdout.trim().is_empty() {
+                            message.push_str(&format!("\n\n{}", stdout.trim_end()));
+                        }
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        if !stderr.trim().is_empty() {
+                            message.push_str(&format!("\n\n{}", stderr.trim_end()));
+                        }
+                        if !output.status.success() {
+                            message.push_str(&format!("\n\n{}", output.status));
+                        }
+                        // The command may have created or removed visible entries
+                        self.left_pane.refresh()?;
+                        self.right_pane.refresh()?;
+                        self.current_dialog = Some(DialogType::Info {
+                            title: "Command output".to_string(),
+                            message,
+                        });
+                    },
+                    Err(e) => {
+                        self.show_error(format!("Cannot run command: {}", e));
+                    }
+                }
+            },
             InputAction::SelectByPattern => {
-                if !input.trim().is_empty() {
-                    match self.get_active_pane_mut().select_by_pattern(input.trim()) {
+                let trimmed = input.trim().to_string();
+                if let Some(list_path) = trimmed.strip_prefix('@') {
+                    // "@paths.txt" selects the entries named in that file,
+                    // one name or full path per line
+                    match std::fs::read_to_string(list_path.trim()) {
+                        Ok(content) => {
+                            let names: Vec<String> = content
+                                .lines()
+                                .map(|line| line.trim().to_string())
+                                .filter(|line| !line.is_empty())
+                                .collect();
+                            let count = self.get_active_pane_mut().select_named(&names);
+                            if count == 0 {
+                                self.show_toast("No entries from the list are in this directory".to_string());
+                            } else {
+                                self.show_toast(format!("Selected {} entries from the list", count));
+                            }
+                        },
+                        Err(e) => {
+                            self.show_error(format!("Cannot read list file: {}", e));
+                        }
+                    }
+                } else if !trimmed.is_empty() {
+                    match self.get_active_pane_mut().select_by_pattern(&trimmed) {
                         Ok(count) => {
                             if count == 0 {
-                                self.show_error("No files matched the pattern".to_string());
+                                self.show_toast("No files matched the pattern".to_string());
+                            } else {
+                                self.show_toast(format!("Selected {} file(s)", count));
                             }
                         },
                         Err(e) => {
@@ -694,102 +1798,1126 @@ impl App {
         Ok(())
     }
 
-    fn show_error(&mut self, message: String) {
-        self.current_dialog = Some(DialogType::Error { message });
-    }
+    /// Pre-scan the file count and total size of the given paths on a
+    /// background thread, showing a "calculating..." dialog. Esc skips the
+    /// scan and falls back to indeterminate totals (0, 0).
+    fn prescan_totals(&mut self, sources: &[std::path::PathBuf]) -> Result<(u64, u64)> {
+        let rx = scan_total_size_background(sources.to_vec());
+        let mut last = (0u64, 0u64);
 
-    fn cleanup(&mut self) -> Result<()> {
-        disable_raw_mode()?;
-        execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
-        self.terminal.show_cursor()?;
-        Ok(())
-    }
-}
+        loop {
+            self.current_dialog = Some(DialogType::Scanning { scanned: last.1 });
+            self.draw()?;
 
-fn render_pane<B: tui::backend::Backend>(
-    f: &mut Frame<B>, 
-    area: Rect, 
-    pane: &PaneState, 
-    is_active: bool, 
-    _config: &Config
-) {
-    // Calculate approximate column widths for right-alignment formatting
-    let total_width = area.width.saturating_sub(4); // Account for borders and spacing
-    let size_width = (total_width * 15 / 100).max(8) as usize; // 15% of space, minimum 8 chars
-    let date_width = (total_width * 20 / 100).max(16) as usize; // 20% of space, minimum 16 chars for "MMM dd, HH:mm"
+            if let Ok(true) = event::poll(std::time::Duration::from_millis(50)) {
+                if let Ok(event::Event::Key(key)) = event::read() {
+                    if key.kind == KeyEventKind::Press && key.code == KeyCode::Esc {
+                        self.current_dialog = None;
+                        return Ok((0, 0));
+                    }
+                }
+            }
 
-    // Create rows for the table
-    let rows: Vec<Row> = pane.entries.iter()
-        .enumerate()
-        .map(|(i, entry)| {
-            let mut style = if entry.is_dir {
-                Style::default().fg(Color::White).bg(Color::Blue).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::Cyan).bg(Color::Blue)
-            };
+            loop {
+                match rx.try_recv() {
+                    Ok(totals) => last = totals,
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        self.current_dialog = None;
+                        return Ok(last);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pre-scan the totals for `operation`, then hand it to a worker thread
+    /// and show a live progress dialog driven by `poll_operation`.
+    fn start_operation(&mut self, mut operation: FileOperation) -> Result<()> {
+        let (files, bytes) = self.prescan_totals(&operation.source_files)?;
+        operation.files_total = files;
+        operation.total_size = bytes;
+        operation.dereference_symlinks = self.config.general.follow_symlinks;
+        operation.audit_file = self.config.logging.audit_file.clone();
 
-            // Highlight selected items with black background
-            if pane.selected_indices.contains(&i) {
-                style = style.bg(Color::Black).fg(Color::White);
+        // Preflight warnings gated by the [Confirmation] toggles: existing
+        // destination entries, and unusually large batches
+        let mut warnings = Vec::new();
+        if operation.operation_type != crate::core::OperationType::Delete && self.config.confirm_overwrite() {
+            let collisions = operation.source_files.iter()
+                .filter(|source| {
+                    source.file_name()
+                        .map(|name| operation.destination.join(name).exists())
+                        .unwrap_or(false)
+                })
+                .count();
+            if collisions > 0 {
+                warnings.push(format!("{} existing item(s) at the destination will be overwritten.", collisions));
+            }
+        }
+        // Sources whose names differ only by letter case cannot coexist on a
+        // case-insensitive destination volume; one would silently replace the
+        // other half-way through the operation
+        if operation.operation_type != crate::core::OperationType::Delete
+            && operation.source_files.len() > 1
+            && platform::is_case_insensitive_fs(&operation.destination)
+        {
+            let mut seen: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+            let mut clashes = Vec::new();
+            for source in &operation.source_files {
+                let name = match source.file_name() {
+                    Some(name) => name.to_string_lossy().to_string(),
+                    None => continue,
+                };
+                let folded = name.to_lowercase();
+                match seen.get(&folded) {
+                    Some(previous) if *previous != name => {
+                        clashes.push(format!("'{}' / '{}'", previous, name));
+                    },
+                    Some(_) => {},
+                    None => {
+                        seen.insert(folded, name);
+                    },
+                }
             }
+            if !clashes.is_empty() {
+                warnings.push(format!(
+                    "The destination ignores name case; these sources would collide: {}.",
+                    clashes.join(", ")
+                ));
+            }
+        }
+        let bulk_files = self.config.confirmation.bulk_files;
+        if bulk_files > 0 && operation.files_total > bulk_files {
+            warnings.push(format!("This operation involves {} files.", operation.files_total));
+        }
+        let bulk_size = self.config.confirmation.bulk_size_mb * 1024 * 1024;
+        if bulk_size > 0 && operation.total_size > bulk_size {
+            warnings.push(format!(
+                "This operation touches {}.",
+                platform::format_file_size(operation.total_size)
+            ));
+        }
+        if !warnings.is_empty() {
+            let message = format!("{}\n\nContinue?", warnings.join("\n"));
+            self.pending_operation = Some(operation);
+            self.current_dialog = Some(DialogType::Confirm {
+                message,
+                action: ConfirmAction::ProceedChecks,
+            });
+            return Ok(());
+        }
 
-            let icon = if entry.name == ".." {
-                "↑"
-            } else if entry.is_dir {
-                "📁"
-            } else if entry.is_archive {
-                "📦"
-            } else {
-                "📄"
-            };
+        self.check_space_and_launch(operation)
+    }
 
-            let name_cell = format!("{} {}", icon, entry.name);
-            
-            // Right-align size text within its column width
-            let size_raw = if entry.is_dir {
-                "<DIR>".to_string()
+    /// Final leg of starting an operation: warn when the destination volume
+    /// looks too small, then hand off to a worker thread
+    fn check_space_and_launch(&mut self, operation: FileOperation) -> Result<()> {
+        // Warn up front when the destination volume doesn't have room,
+        // instead of failing halfway through with a disk-full error
+        if operation.operation_type != crate::core::OperationType::Delete && operation.total_size > 0 {
+            if let Ok(free) = platform::get_free_disk_space(&operation.destination) {
+                if operation.total_size > free {
+                    let message = format!(
+                        "Not enough free space on the destination:\nneeded {}, available {}.\n\nTry anyway?",
+                        platform::format_file_size(operation.total_size),
+                        platform::format_file_size(free)
+                    );
+                    self.pending_operation = Some(operation);
+                    self.current_dialog = Some(DialogType::Confirm {
+                        message,
+                        action: ConfirmAction::ProceedLowSpace,
+                    });
+                    return Ok(());
+                }
+            }
+        }
+
+        self.launch_operation(operation)
+    }
+
+    /// Hand a fully prepared operation to a worker thread and show the
+    /// progress dialog
+    fn launch_operation(&mut self, operation: FileOperation) -> Result<()> {
+        // Persist the operation so an exit or crash mid-way can offer to
+        // resume it on the next start
+        if let Err(e) = save_operation_state(&self.operation_state_file, &operation) {
+            log::warn!("Failed to persist operation state: {}", e);
+        }
+        self.current_dialog = Some(DialogType::Progress { operation: operation.clone() });
+        let (shared, handle) = spawn_operation(operation);
+        self.active_operation = Some(shared);
+        self.operation_handle = Some(handle);
+        Ok(())
+    }
+
+    /// Refresh the progress dialog from the worker's shared state and finish
+    /// up (refresh panes, surface errors) once the worker is done.
+    fn poll_operation(&mut self) -> Result<()> {
+        let shared = match &self.active_operation {
+            Some(shared) => std::sync::Arc::clone(shared),
+            None => return Ok(()),
+        };
+
+        // Check for completion before taking the snapshot: a worker that
+        // finishes in between would otherwise have its last warnings and
+        // progress reported from a stale clone, with no later poll to
+        // correct the final report
+        let finished = self.operation_handle
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(true);
+
+        if !finished {
+            let snapshot = shared.lock().unwrap().clone();
+            if matches!(self.current_dialog, Some(DialogType::Progress { .. }) | None) {
+                self.current_dialog = Some(DialogType::Progress { operation: snapshot });
+            }
+            return Ok(());
+        }
+
+        if matches!(self.current_dialog, Some(DialogType::Progress { .. })) {
+            self.current_dialog = None;
+        }
+
+        let result = match self.operation_handle.take() {
+            Some(handle) => handle.join().unwrap_or_else(|_| {
+                Err(GeekCommanderError::FileOperation("Operation worker panicked".to_string()))
+            }),
+            None => Ok(()),
+        };
+
+        // The join above makes this snapshot the worker's final state
+        let snapshot = shared.lock().unwrap().clone();
+        self.active_operation = None;
+
+        clear_operation_state(&self.operation_state_file);
+
+        if self.quit_when_idle {
+            self.should_quit = true;
+        }
+
+        self.left_pane.refresh()?;
+        self.right_pane.refresh()?;
+        self.get_active_pane_mut().deselect_all();
+
+        if let Err(e) = result {
+            self.show_error(format!("{:?} failed: {}", snapshot.operation_type, e));
+        } else if !snapshot.warnings.is_empty() {
+            self.current_dialog = Some(DialogType::Info {
+                title: format!("{:?} finished with warnings", snapshot.operation_type),
+                message: snapshot.warnings.join("\n"),
+            });
+        } else if !snapshot.cancelled {
+            self.show_toast(format!(
+                "{:?} finished ({} file(s))",
+                snapshot.operation_type, snapshot.files_completed
+            ));
+        }
+        Ok(())
+    }
+
+    fn perform_copy(&mut self, exclude_patterns: Vec<String>) -> Result<()> {
+        let dest = self.get_inactive_pane().current_path.clone();
+        let selected = self.get_active_pane_mut().get_selected_entries();
+        let sources = if selected.is_empty() {
+            if let Some(current) = self.get_active_pane_mut().get_current_entry() {
+                vec![current]
+            } else {
+                return Ok(());
+            }
+        } else {
+            selected
+        };
+
+        match copy_files_with_excludes(&sources, &dest, exclude_patterns) {
+            Ok(operation) => {
+                self.start_operation(operation)?;
+            },
+            Err(e) => {
+                self.show_error(format!("Copy failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Move any pane whose directory disappeared (e.g. after an unmount) back
+    /// to the nearest existing ancestor, falling back to the home directory.
+    fn recover_unmounted_panes(&mut self) -> Result<()> {
+        for pane in [&mut self.left_pane, &mut self.right_pane] {
+            if pane.current_path.exists() {
+                continue;
+            }
+
+            let mut fallback = pane.current_path.clone();
+            while !fallback.exists() {
+                match fallback.parent() {
+                    Some(parent) => fallback = parent.to_path_buf(),
+                    None => {
+                        fallback = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/"));
+                        break;
+                    }
+                }
+            }
+
+            pane.current_path = fallback;
+            pane.cursor_index = 0;
+            pane.selected_indices.clear();
+            pane.refresh()?;
+        }
+        Ok(())
+    }
+
+    fn collect_delete_sources(&self) -> Vec<FileEntry> {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let selected = pane.get_selected_entries();
+        if selected.is_empty() {
+            pane.get_current_entry().cloned().into_iter().collect()
+        } else {
+            selected.into_iter().cloned().collect()
+        }
+    }
+
+    fn perform_delete(&mut self) -> Result<()> {
+        let sources = self.collect_delete_sources();
+        if sources.is_empty() {
+            return Ok(());
+        }
+        let source_refs: Vec<&FileEntry> = sources.iter().collect();
+
+        match delete_files(&source_refs) {
+            Ok(operation) => {
+                self.start_operation(operation)?;
+            },
+            Err(e) => {
+                self.show_error(format!("Delete failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill in the recursive stats in a pending delete confirmation once the
+    /// background scan finishes.
+    fn poll_delete_stats(&mut self) {
+        let stats = match self.pending_delete_stats.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            Some(stats) => stats,
+            None => return,
+        };
+        self.pending_delete_stats = None;
+
+        if let Some(DialogType::Confirm { message, action: ConfirmAction::DeleteRecursive }) = self.current_dialog.as_mut() {
+            let (file_count, total_size) = stats;
+            *message = message.replace(
+                "Contents: calculating...",
+                &format!("Contents: {} files, {}", file_count, platform::format_file_size(total_size)),
+            );
+        }
+    }
+
+    /// Start a background size scan when a pane shows a new directory and
+    /// `ShowDirSizes` is on, cancelling any walk still running for the old one
+    fn maybe_request_dir_sizes(&mut self) {
+        if !self.config.general.show_dir_sizes {
+            return;
+        }
+
+        let current = (self.left_pane.current_path.clone(), self.right_pane.current_path.clone());
+        if self.dir_size_paths.as_ref() == Some(&current) {
+            self.apply_cached_dir_sizes();
+            return;
+        }
+
+        if let Some(cancel) = self.dir_size_cancel.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        let mut dirs = Vec::new();
+        for pane in [&self.left_pane, &self.right_pane] {
+            for entry in &pane.entries {
+                if entry.is_dir && entry.name != ".." && !self.dir_size_cache.contains_key(&entry.path) {
+                    dirs.push(entry.path.clone());
+                }
+            }
+        }
+
+        self.dir_size_paths = Some(current);
+        if !dirs.is_empty() {
+            let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+            self.dir_size_rx = Some(spawn_dir_size_scan(dirs, cancel.clone()));
+            self.dir_size_cancel = Some(cancel);
+        }
+        self.apply_cached_dir_sizes();
+    }
+
+    /// Drain finished directory sizes into the cache and the visible entries
+    fn poll_dir_sizes(&mut self) {
+        let mut results = Vec::new();
+        let mut done = false;
+        if let Some(ref rx) = self.dir_size_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(result) => results.push(result),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        done = true;
+                        break;
+                    },
+                }
+            }
+        }
+        if done {
+            self.dir_size_rx = None;
+            self.dir_size_cancel = None;
+        }
+        if !results.is_empty() {
+            self.dir_size_cache.extend(results);
+            self.apply_cached_dir_sizes();
+        }
+    }
+
+    /// Start a background child-count scan when a pane shows a new directory
+    /// and `ShowDirCounts` is on, cancelling any scan still running
+    fn maybe_request_dir_counts(&mut self) {
+        if !self.config.general.show_dir_counts {
+            return;
+        }
+
+        let current = (self.left_pane.current_path.clone(), self.right_pane.current_path.clone());
+        if self.dir_count_paths.as_ref() == Some(&current) {
+            self.apply_cached_dir_counts();
+            return;
+        }
+
+        if let Some(cancel) = self.dir_count_cancel.take() {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        let mut dirs = Vec::new();
+        for pane in [&self.left_pane, &self.right_pane] {
+            for entry in &pane.entries {
+                if entry.is_dir && entry.name != ".." && !self.dir_count_cache.contains_key(&entry.path) {
+                    dirs.push(entry.path.clone());
+                }
+            }
+        }
+
+        self.dir_count_paths = Some(current);
+        if !dirs.is_empty() {
+            let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+            self.dir_count_rx = Some(spawn_dir_count_scan(dirs, cancel.clone()));
+            self.dir_count_cancel = Some(cancel);
+        }
+        self.apply_cached_dir_counts();
+    }
+
+    /// Drain finished child counts into the cache and the visible entries
+    /// Swap in the initial pane listings as their background reads finish.
+    /// A pane that fails to load stops spinning and surfaces the error; the
+    /// user can still navigate elsewhere from it.
+    fn poll_pane_loads(&mut self) {
+        let mut results = Vec::new();
+        let mut done = false;
+        if let Some(ref rx) = self.pane_load_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(result) => results.push(result),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        done = true;
+                        break;
+                    },
+                }
+            }
+        }
+        for (index, result) in results {
+            match result {
+                Ok(pane) => {
+                    if index == 0 {
+                        self.left_pane = pane;
+                    } else {
+                        self.right_pane = pane;
+                    }
+                },
+                Err(e) => {
+                    let pane = if index == 0 { &mut self.left_pane } else { &mut self.right_pane };
+                    pane.loading = false;
+                    self.show_error(format!("Cannot read start directory: {}", e));
+                },
+            }
+        }
+        if done || (!self.left_pane.loading && !self.right_pane.loading) {
+            self.pane_load_rx = None;
+        }
+    }
+
+    fn poll_dir_counts(&mut self) {
+        let mut results = Vec::new();
+        let mut done = false;
+        if let Some(ref rx) = self.dir_count_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(result) => results.push(result),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        done = true;
+                        break;
+                    },
+                }
+            }
+        }
+        if done {
+            self.dir_count_rx = None;
+            self.dir_count_cancel = None;
+        }
+        if !results.is_empty() {
+            self.dir_count_cache.extend(results);
+            self.apply_cached_dir_counts();
+        }
+    }
+
+    fn apply_cached_dir_counts(&mut self) {
+        for pane in [&mut self.left_pane, &mut self.right_pane] {
+            for entry in pane.entries.iter_mut() {
+                if entry.is_dir && entry.name != ".." && entry.child_count.is_none() {
+                    entry.child_count = self.dir_count_cache.get(&entry.path).copied();
+                }
+            }
+        }
+    }
+
+    /// Open the viewer once a pending directory summary arrives
+    fn poll_dir_summary(&mut self) {
+        let content = match &self.dir_summary_rx {
+            Some(rx) => match rx.try_recv() {
+                Ok(content) => Some(content),
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+            },
+            None => return,
+        };
+
+        self.dir_summary_rx = None;
+        let path = self.dir_summary_path.take();
+        if let Some(content) = content {
+            let title = path
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "directory summary".to_string());
+            self.viewer = Some(FileViewer::from_content(title, content));
+            self.mode = AppMode::Viewer;
+        }
+    }
+
+    fn apply_cached_dir_sizes(&mut self) {
+        for pane in [&mut self.left_pane, &mut self.right_pane] {
+            for entry in pane.entries.iter_mut() {
+                if entry.is_dir && entry.name != ".." && entry.dir_size.is_none() {
+                    entry.dir_size = self.dir_size_cache.get(&entry.path).copied();
+                }
+            }
+        }
+    }
+
+    fn show_error(&mut self, message: String) {
+        self.current_dialog = Some(DialogType::Error { message });
+    }
+
+    /// Describe the running operation and offer to wait for it, cancel it,
+    /// or abort the quit, instead of tearing the terminal down mid-copy
+    fn confirm_quit_with_jobs(&mut self) {
+        let message = match &self.active_operation {
+            Some(shared) => {
+                let op = shared.lock().unwrap();
+                format!(
+                    "A {:?} operation is still running:\n  {} / {} file(s), {} of {}\n\n\
+                     (W)ait and quit when it finishes\n(C)ancel it and quit\n(Esc) keep working",
+                    op.operation_type,
+                    op.files_completed,
+                    op.files_total,
+                    platform::format_file_size(op.processed_size),
+                    platform::format_file_size(op.total_size),
+                )
+            }
+            None => return,
+        };
+        self.current_dialog = Some(DialogType::QuitWithJobs { message });
+    }
+
+    /// Show a short-lived message on the status line instead of a modal
+    /// dialog, and keep it in the Alt+M message history
+    fn show_toast(&mut self, message: String) {
+        self.message_history.push(format!("{} {}", chrono::Local::now().format("%H:%M:%S"), message));
+        self.toast = Some((message, std::time::Instant::now()));
+    }
+
+    /// Open the record of past toast messages in the viewer
+    fn show_message_history(&mut self) {
+        let content = if self.message_history.is_empty() {
+            "No messages yet".to_string()
+        } else {
+            self.message_history.join("\n")
+        };
+        self.viewer = Some(FileViewer::from_content("Messages".to_string(), content));
+        self.mode = AppMode::Viewer;
+    }
+
+    /// Captions for the bottom F-key bar, generated from the keybinding
+    /// config and adjusted for the current context
+    fn function_key_labels(&self) -> [String; 10] {
+        let mut labels: [String; 10] = Default::default();
+
+        if self.mode == AppMode::Viewer {
+            labels[9] = "Exit".to_string();
+            return labels;
+        }
+
+        let kb = &self.config.keybindings;
+        let actions: [(&KeyBinding, &str); 9] = [
+            (&kb.help, "Help"),
+            (&kb.view, "View"),
+            (&kb.edit, "Edit"),
+            (&kb.copy, "Copy"),
+            (&kb.move_files, "Move"),
+            (&kb.new_dir, "MkDir"),
+            (&kb.delete, "Delete"),
+            (&kb.rename, "Rename"),
+            (&kb.quit, "Quit"),
+        ];
+        for (binding, label) in actions {
+            if binding.modifiers.is_empty() {
+                if let KeyCode::F(n) = binding.code {
+                    if (1..=10).contains(&n) {
+                        labels[(n - 1) as usize] = label.to_string();
+                    }
+                }
+            }
+        }
+
+        // F9 always opens the context menu (hardcoded in handle_key_event)
+        labels[8] = "Menu".to_string();
+
+        // Inside an archive, F5 extracts rather than copies
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        if pane.archive_context.is_some() {
+            for label in labels.iter_mut() {
+                if label == "Copy" {
+                    *label = "Extract".to_string();
+                }
+            }
+        }
+
+        labels
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        // Clicks on the bottom bar act like pressing the F-key, in any mode
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+            let size = self.terminal.size()?;
+            if mouse.row + 1 == size.height && self.current_dialog.is_none() {
+                let button = (mouse.column as usize * 10 / size.width.max(1) as usize).min(9);
+                return self.handle_key_event(KeyCode::F(button as u8 + 1), KeyModifiers::NONE);
+            }
+        }
+
+        if self.mode != AppMode::Normal {
+            return Ok(());
+        }
+
+        if let Some(DialogType::ContextMenu { x, y, .. }) = self.current_dialog.clone() {
+            match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let rect = context_menu_rect(x, y, self.terminal.size()?);
+                    self.current_dialog = None;
+                    // Clicks on a menu item execute it; clicks elsewhere close the menu
+                    if mouse.column > rect.x && mouse.column < rect.x + rect.width - 1
+                        && mouse.row > rect.y && mouse.row < rect.y + rect.height - 1
+                    {
+                        let item = (mouse.row - rect.y - 1) as usize;
+                        if item < CONTEXT_MENU_ITEMS.len() {
+                            self.execute_context_menu_item(item)?;
+                        }
+                    }
+                    return Ok(());
+                },
+                MouseEventKind::Down(MouseButton::Right) => {
+                    // Re-open at the new position below
+                    self.current_dialog = None;
+                },
+                _ => return Ok(()),
+            }
+        } else if self.current_dialog.is_some() {
+            // Other dialogs are keyboard-driven
+            return Ok(());
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.is_on_splitter(mouse.column, mouse.row) {
+                    self.dragging_splitter = true;
+                } else if mouse.row == 2 {
+                    // The table header sits on the row right below the pane border
+                    if let Some((pane, column)) = self.pane_header_column_at(mouse.column) {
+                        self.handle_sort_click(pane, column)?;
+                    }
+                } else if let Some((pane, index)) = self.pane_entry_at(mouse.column, mouse.row) {
+                    self.active_pane = pane;
+                    self.get_active_pane_mut().cursor_index = index;
+                }
+            },
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_splitter => {
+                let width = self.terminal.size()?.width.max(1);
+                let percent = (mouse.column as u32 * 100 / width as u32) as u16;
+                self.config.panels.split = percent.clamp(10, 90);
+            },
+            MouseEventKind::Up(MouseButton::Left) => {
+                if self.dragging_splitter {
+                    self.dragging_splitter = false;
+                    self.persist_split();
+                }
+            },
+            MouseEventKind::Down(MouseButton::Right) => {
+                if let Some((pane, index)) = self.pane_entry_at(mouse.column, mouse.row) {
+                    self.active_pane = pane;
+                    self.get_active_pane_mut().cursor_index = index;
+                    self.current_dialog = Some(DialogType::ContextMenu {
+                        selected: 0,
+                        x: mouse.column,
+                        y: mouse.row,
+                    });
+                }
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Screen column where the pane divider sits, per the current split
+    fn splitter_column(&self, width: u16) -> u16 {
+        width * self.config.panels.split / 100
+    }
+
+    /// Whether a click lands on (or right next to) the pane divider
+    fn is_on_splitter(&mut self, column: u16, row: u16) -> bool {
+        let size = match self.terminal.size() {
+            Ok(size) => size,
+            Err(_) => return false,
+        };
+        if row == 0 || row >= size.height.saturating_sub(2) {
+            return false;
+        }
+        let divider = self.splitter_column(size.width);
+        column + 1 >= divider && column <= divider
+    }
+
+    /// Adjust the pane split by `delta` percent and persist the new value
+    fn adjust_split(&mut self, delta: i16) {
+        let new = (self.config.panels.split as i16 + delta).clamp(10, 90) as u16;
+        if new != self.config.panels.split {
+            self.config.panels.split = new;
+            self.persist_split();
+        }
+    }
+
+    fn persist_split(&mut self) {
+        if let Err(e) = self.config.save() {
+            log::warn!("Failed to save pane split: {}", e);
+        }
+    }
+
+    /// Map a click on the header row to (pane index, sort column), mirroring
+    /// the percentage widths and column spacing used in render_pane
+    fn pane_header_column_at(&mut self, column: u16) -> Option<(usize, SortColumn)> {
+        let size = self.terminal.size().ok()?;
+        let divider = self.splitter_column(size.width);
+        let (pane_idx, pane_x, pane_width) = if column < divider {
+            (0, 0, divider)
+        } else {
+            (1, divider, size.width.saturating_sub(divider))
+        };
+        let inner = pane_width.saturating_sub(2);
+        let x = column.checked_sub(pane_x + 1)?;
+        if x >= inner {
+            return None;
+        }
+        let name_percent = if self.config.general.show_permissions { 53 } else { 65 };
+        let name_width = inner * name_percent / 100;
+        let size_width = inner * 15 / 100;
+        let date_width = inner * 20 / 100;
+        if x < name_width {
+            Some((pane_idx, SortColumn::Name))
+        } else if x < name_width + 1 + size_width {
+            Some((pane_idx, SortColumn::Size))
+        } else if x < name_width + size_width + 2 + date_width {
+            Some((pane_idx, SortColumn::Modified))
+        } else {
+            None
+        }
+    }
+
+    /// Sort the clicked pane by the clicked column (again to reverse),
+    /// keeping the cursor on the same entry across the re-order
+    fn handle_sort_click(&mut self, pane_idx: usize, column: SortColumn) -> Result<()> {
+        let pane = if pane_idx == 0 { &mut self.left_pane } else { &mut self.right_pane };
+        let current = pane.get_current_entry().map(|e| e.name.clone());
+        pane.toggle_sort(column);
+        pane.refresh()?;
+        if let Some(name) = current {
+            pane.focus_entry(&name);
+        }
+        let label = match column {
+            SortColumn::Name => "name",
+            SortColumn::Size => "size",
+            SortColumn::Modified => "date",
+        };
+        let direction = if pane.sort_reversed { "descending" } else { "ascending" };
+        self.show_toast(format!("Sorted by {} ({})", label, direction));
+        Ok(())
+    }
+
+    /// Map a screen position to (pane index, entry index), mirroring the
+    /// layout in draw(): title row, pane border, header row, then entries.
+    fn pane_entry_at(&mut self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let size = self.terminal.size().ok()?;
+        let visible = size.height.saturating_sub(6) as usize;
+        if row < 3 || (row as usize) >= 3 + visible {
+            return None;
+        }
+        let pane_idx = if column < self.splitter_column(size.width) { 0 } else { 1 };
+        let pane = if pane_idx == 0 { &self.left_pane } else { &self.right_pane };
+        // The table widget scrolls only as far as needed to keep the cursor visible
+        let offset = (pane.cursor_index + 1).saturating_sub(visible);
+        let index = offset + (row as usize - 3);
+        if index < pane.entries.len() {
+            Some((pane_idx, index))
+        } else {
+            None
+        }
+    }
+
+    /// Open the context menu next to the cursor entry, for keyboard users
+    fn open_context_menu_at_cursor(&mut self) -> Result<()> {
+        let size = self.terminal.size()?;
+        let visible = size.height.saturating_sub(6) as usize;
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        if pane.entries.is_empty() {
+            return Ok(());
+        }
+        // On terminals too short to show any entries `visible` is zero and
+        // the offset exceeds the cursor index; saturate instead of panicking
+        // and keep the menu inside the screen
+        let offset = (pane.cursor_index + 1).saturating_sub(visible);
+        let y = (3 + pane.cursor_index.saturating_sub(offset) as u16)
+            .min(size.height.saturating_sub(2));
+        let x = if self.active_pane == 0 { 4 } else { self.splitter_column(size.width) + 4 };
+        self.current_dialog = Some(DialogType::ContextMenu { selected: 0, x, y });
+        Ok(())
+    }
+
+    fn execute_context_menu_item(&mut self, item: usize) -> Result<()> {
+        match CONTEXT_MENU_ITEMS[item] {
+            "View" => self.handle_view(),
+            "Edit" => self.handle_edit(),
+            "Copy" => self.handle_copy(),
+            "Move" => self.handle_move(),
+            "Delete" => self.handle_delete(),
+            "Rename" => self.handle_rename(),
+            "Properties" => self.handle_info(),
+            "Open With" => self.handle_open_with(),
+            "Zip Comment" => self.handle_edit_zip_comment(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Context menu "Zip Comment": edit the archive comment of the ZIP
+    /// under the cursor, prefilled with what is currently stored
+    fn handle_edit_zip_comment(&mut self) -> Result<()> {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let path = match pane.get_current_entry() {
+            Some(entry) if is_zip_archive(&entry.path) => entry.path.clone(),
+            _ => {
+                self.show_toast("Not a ZIP archive".to_string());
+                return Ok(());
+            }
+        };
+        let current = match crate::archive::zip_comment(&path) {
+            Ok(comment) => comment,
+            Err(e) => {
+                self.show_error(format!("Cannot read archive: {}", e));
+                return Ok(());
+            }
+        };
+        self.current_dialog = Some(DialogType::Input {
+            prompt: format!("Comment for {}:", platform::path_to_display_string(&path)),
+            input: current,
+            action: InputAction::EditZipComment,
+        });
+        Ok(())
+    }
+
+    fn handle_open_with(&mut self) -> Result<()> {
+        let pane = if self.active_pane == 0 { &self.left_pane } else { &self.right_pane };
+        let path = match pane.get_current_entry() {
+            Some(entry) if entry.name != ".." => entry.path.clone(),
+            _ => return Ok(()),
+        };
+        if let Err(e) = platform::open_with_default_app(&path) {
+            self.show_error(format!("Failed to open: {}", e));
+        } else if path.is_file() {
+            self.record_recent_file(path);
+        }
+        Ok(())
+    }
+
+}
+
+/// Styles resolved from the configured color scheme, or a monochrome
+/// fallback when `UseColors=false`
+struct PaneStyles {
+    background: Style,
+    directory: Style,
+    file: Style,
+    selected: Style,
+    cursor: Style,
+    /// Transient highlight for entries that just appeared in the directory
+    recent: Style,
+    header: Style,
+    active_border: Style,
+    inactive_border: Style,
+    status_bar: Style,
+}
+
+fn resolve_styles(config: &Config) -> PaneStyles {
+    let colors = &config.colors;
+    if config.general.use_colors {
+        let bg = Color::Blue;
+        PaneStyles {
+            background: Style::default().bg(bg),
+            directory: Style::default().fg(colors.directory_fg).bg(bg).add_modifier(Modifier::BOLD),
+            file: Style::default().fg(colors.file_fg).bg(bg),
+            selected: if config.general.high_contrast_selection {
+                Style::default()
+                    .bg(colors.selected_item)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD)
+            } else {
+                Style::default().bg(colors.selected_item).fg(Color::White)
+            },
+            cursor: Style::default().bg(colors.cursor_bg).fg(Color::Black).add_modifier(Modifier::BOLD),
+            recent: Style::default().fg(Color::Green).bg(bg).add_modifier(Modifier::BOLD),
+            header: Style::default().fg(Color::Yellow).bg(bg).add_modifier(Modifier::BOLD),
+            active_border: Style::default().fg(colors.active_pane_border).bg(bg),
+            inactive_border: Style::default().fg(colors.inactive_pane_border).bg(bg),
+            status_bar: Style::default().fg(colors.status_bar).bg(bg),
+        }
+    } else {
+        // Monochrome: terminal default colors, with reverse video standing in
+        // for the cursor bar and selection highlight
+        PaneStyles {
+            background: Style::default(),
+            directory: Style::default().add_modifier(Modifier::BOLD),
+            file: Style::default(),
+            selected: Style::default().add_modifier(Modifier::REVERSED),
+            cursor: Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            recent: Style::default().add_modifier(Modifier::UNDERLINED),
+            header: Style::default().add_modifier(Modifier::BOLD),
+            active_border: Style::default().add_modifier(Modifier::BOLD),
+            inactive_border: Style::default(),
+            status_bar: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+}
+
+/// Render the NC-style bottom bar of ten labeled F-key buttons
+fn render_function_key_bar<B: tui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    labels: &[String; 10],
+    config: &Config,
+) {
+    let (number_style, caption_style) = if config.general.use_colors {
+        (
+            Style::default().fg(Color::White).bg(Color::Black),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )
+    } else {
+        (Style::default(), Style::default().add_modifier(Modifier::REVERSED))
+    };
+
+    let button_width = (area.width as usize / 10).max(3);
+    let caption_width = button_width - 2;
+
+    let mut spans = Vec::new();
+    for (i, label) in labels.iter().enumerate() {
+        spans.push(Span::styled(format!("{:>2}", i + 1), number_style));
+        spans.push(Span::styled(
+            format!("{:<width$.width$}", label, width = caption_width),
+            caption_style,
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Spans::from(spans)), area);
+}
+
+fn render_pane<B: tui::backend::Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    pane: &PaneState,
+    is_active: bool,
+    config: &Config,
+    octal_permissions: bool,
+) {
+    let styles = resolve_styles(config);
+    let show_permissions = config.general.show_permissions;
+
+    // Calculate approximate column widths for right-alignment formatting
+    let total_width = area.width.saturating_sub(4); // Account for borders and spacing
+    let size_width = (total_width * 15 / 100).max(8) as usize; // 15% of space, minimum 8 chars
+    let date_width = (total_width * 20 / 100).max(16) as usize; // 20% of space, minimum 16 chars for "MMM dd, HH:mm"
+
+    // Create rows for the table
+    let rows: Vec<Row> = pane.entries.iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let mut style = if entry.is_dir {
+                styles.directory
+            } else {
+                styles.file
+            };
+
+            // Entries that just appeared get a short-lived highlight so
+            // arriving downloads or build outputs stand out
+            if pane.is_recent_entry(&entry.name) {
+                style = styles.recent;
+            }
+
+            // Highlight selected items with the configured selection color
+            let is_selected = pane.selected_indices.contains(&i);
+            if is_selected {
+                style = styles.selected;
+            }
+
+            let icon = if entry.name == ".." {
+                "↑"
+            } else if entry.is_dir {
+                "📁"
+            } else if entry.is_archive {
+                "📦"
+            } else {
+                "📄"
+            };
+
+            // Leading `*` marker so selection is readable without color
+            let marker = if is_selected { "*" } else { " " };
+            let name_cell = format!("{}{} {}", marker, icon, entry.name);
+            
+            // Right-align size text within its column width
+            let mut size_raw = if entry.is_dir {
+                // Show the recursive size once the background scan has it
+                match (entry.dir_size, entry.child_count) {
+                    (Some(size), _) => platform::format_file_size(size),
+                    // Immediate child count from the lighter-weight scan
+                    (None, Some(count)) => format!("<{}>", count),
+                    (None, None) => "<DIR>".to_string(),
+                }
             } else {
                 platform::format_file_size(entry.size)
             };
+            if config.general.show_link_count && !entry.is_dir && entry.nlink > 1 {
+                size_raw = format!("{}={}", entry.nlink, size_raw);
+            }
             let size_text = format!("{:>width$}", size_raw, width = size_width);
 
             // Left-align date text (no padding needed)
             let date_text = platform::format_file_time(entry.modified);
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(name_cell), // Left-aligned name column
                 Cell::from(size_text), // Right-aligned size column
                 Cell::from(date_text), // Right-aligned date column
-            ]).style(style)
+            ];
+            if show_permissions {
+                cells.push(permissions_cell(&entry.permissions, octal_permissions, config));
+            }
+
+            Row::new(cells).style(style)
         })
         .collect();
 
     let border_style = if is_active {
-        Style::default().fg(Color::Cyan).bg(Color::Blue)
+        styles.active_border
+    } else {
+        styles.inactive_border
+    };
+
+    // With FollowSymlinks on, a pane reached through a symlinked directory
+    // also shows where it physically is
+    let physical = if config.general.follow_symlinks && !pane.loading {
+        match pane.current_path.canonicalize() {
+            Ok(canonical) if canonical != pane.current_path => {
+                format!(" \u{2192} {}", platform::path_to_display_string(&canonical))
+            },
+            _ => String::new(),
+        }
+    } else {
+        String::new()
+    };
+
+    // Animated marker while the initial background read is still running;
+    // the run loop redraws often enough to keep it turning
+    let spinner = if pane.loading {
+        let frame = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| (d.as_millis() / 120) as usize % 4)
+            .unwrap_or(0);
+        format!(" [loading {}]", ['|', '/', '-', '\\'][frame])
     } else {
-        Style::default().fg(Color::DarkGray).bg(Color::Blue)
+        String::new()
     };
 
-    let title = format!("{} ({})", 
+    let title = format!("{}{}{}{} ({})",
         platform::path_to_display_string(&pane.current_path),
-        if pane.has_selections() { 
-            format!("{} selected", pane.selected_indices.len()) 
-        } else { 
-            "".to_string() 
+        physical,
+        spinner,
+        if pane.flat_view { " [flat]" } else { "" },
+        if pane.has_selections() {
+            format!("{} selected", pane.selected_indices.len())
+        } else {
+            "".to_string()
         }
     );
 
-    // Create header row with Norton Commander style and right-aligned headers for size/date
-    let header_size = format!("{:>width$}", "Size", width = size_width);
-    let header_date = "Date"; // Left-aligned header
-    
-    let header = Row::new(vec![
-        Cell::from("Name"),
+    // Create header row with Norton Commander style and right-aligned headers
+    // for size/date; the active sort column carries a direction arrow
+    let header_size = format!("{:>width$}", sort_header("Size", SortColumn::Size, pane), width = size_width);
+    let header_date = sort_header("Date", SortColumn::Modified, pane); // Left-aligned header
+
+    let mut header_cells = vec![
+        Cell::from(sort_header("Name", SortColumn::Name, pane)),
         Cell::from(header_size),
         Cell::from(header_date),
-    ])
-    .style(Style::default().fg(Color::Yellow).bg(Color::Blue).add_modifier(Modifier::BOLD))
-    .bottom_margin(0);
+    ];
+    if show_permissions {
+        header_cells.push(Cell::from("Perm"));
+    }
+
+    let header = Row::new(header_cells)
+        .style(styles.header)
+        .bottom_margin(0);
+
+    let widths: &[Constraint] = if show_permissions {
+        &[
+            Constraint::Percentage(53), // Name column
+            Constraint::Percentage(15), // Size column
+            Constraint::Percentage(20), // Date column
+            Constraint::Percentage(12), // Permissions column
+        ]
+    } else {
+        &[
+            Constraint::Percentage(65), // Name column gets 65% of space
+            Constraint::Percentage(15), // Size column gets 15% of space
+            Constraint::Percentage(20), // Date column gets 20% of space
+        ]
+    };
 
     let table = Table::new(rows)
         .header(header)
@@ -797,15 +2925,12 @@ fn render_pane<B: tui::backend::Backend>(
             .borders(Borders::ALL)
             .title(title)
             .border_style(border_style)
-            .style(Style::default().bg(Color::Blue)))
-        .widths(&[
-            Constraint::Percentage(65), // Name column gets 65% of space
-            Constraint::Percentage(15), // Size column gets 15% of space
-            Constraint::Percentage(20), // Date column gets 20% of space
-        ])
+            .style(styles.background))
+        .widths(widths)
         .column_spacing(1)
-        .style(Style::default().bg(Color::Blue))
-        .highlight_style(Style::default().bg(Color::Cyan).fg(Color::Black).add_modifier(Modifier::BOLD));
+        .style(styles.background)
+        .highlight_style(styles.cursor)
+        .highlight_symbol("▶"); // non-color cursor glyph
 
     // Create table state with cursor position
     let mut table_state = tui::widgets::TableState::default();
@@ -816,10 +2941,49 @@ fn render_pane<B: tui::backend::Backend>(
     f.render_stateful_widget(table, area, &mut table_state);
 }
 
-fn render_dialog<B: tui::backend::Backend>(f: &mut Frame<B>, dialog: &DialogType, config: &Config) {
-    let area = centered_rect(60, 20, f.size());
-    f.render_widget(Clear, area);
-    
+/// Header label with a direction arrow when `column` is the pane's
+/// active sort column
+fn sort_header(base: &str, column: SortColumn, pane: &PaneState) -> String {
+    if pane.sort_column == column {
+        format!("{} {}", base, if pane.sort_reversed { "▼" } else { "▲" })
+    } else {
+        base.to_string()
+    }
+}
+
+/// Whether the path looks like a ZIP archive, by extension
+fn is_zip_archive(path: &std::path::Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// Build the permissions cell: octal when toggled, otherwise the symbolic
+/// string with the user/group/other triads colored apart
+fn permissions_cell(permissions: &str, octal: bool, config: &Config) -> Cell<'static> {
+    if octal {
+        return Cell::from(platform::permissions_octal(permissions));
+    }
+    if !config.general.use_colors || permissions.len() != 10 {
+        return Cell::from(permissions.to_string());
+    }
+
+    let bg = Color::Blue;
+    let (file_type, rest) = permissions.split_at(1);
+    let (user, rest) = rest.split_at(3);
+    let (group, other) = rest.split_at(3);
+    Cell::from(Spans::from(vec![
+        Span::raw(file_type.to_string()),
+        Span::styled(user.to_string(), Style::default().fg(Color::Yellow).bg(bg)),
+        Span::styled(group.to_string(), Style::default().fg(Color::Cyan).bg(bg)),
+        Span::styled(other.to_string(), Style::default().fg(Color::Magenta).bg(bg)),
+    ]))
+}
+
+fn render_dialog<B: tui::backend::Backend>(f: &mut Frame<B>, dialog: &DialogType, scroll: u16, config: &Config) {
+    if let DialogType::ContextMenu { selected, x, y } = dialog {
+        render_context_menu(f, *selected, *x, *y, config);
+        return;
+    }
+
     let (title, content) = match dialog {
         DialogType::Help => {
             let help_text = format!(
@@ -844,25 +3008,122 @@ fn render_dialog<B: tui::backend::Backend>(f: &mut Frame<B>, dialog: &DialogType
             ("Help", help_text)
         },
         DialogType::Error { message } => ("Error", format!("{}\n\nPress any key to continue", message)),
+        DialogType::Info { title, message } => (title.as_str(), format!("{}\n\nPress any key to close", message)),
+        DialogType::DriveSelect { drives, selected } => {
+            let mut content = String::new();
+            for (i, drive) in drives.iter().enumerate() {
+                let marker = if i == *selected { ">" } else { " " };
+                let mount = drive.mount_point
+                    .as_ref()
+                    .map(|p| platform::path_to_display_string(p))
+                    .unwrap_or_else(|| "(not mounted)".to_string());
+                let removable = if drive.removable { " [removable]" } else { "" };
+                content.push_str(&format!("{} {:<16} {}{}\n", marker, drive.device, mount, removable));
+            }
+            content.push_str("\nEnter Jump | M Mount | U Unmount | E Eject | Esc Close");
+            ("Drives", content)
+        },
         DialogType::Confirm { message, .. } => ("Confirm", format!("{}\n\n(Y)es / (N)o", message)),
+        DialogType::QuitWithJobs { message } => ("Quit", message.clone()),
         DialogType::Input { prompt, input, .. } => ("Input", format!("{}\n{}_", prompt, input)),
+        DialogType::Scanning { scanned } => (
+            "Please wait",
+            format!(
+                "Calculating total size...\n{} so far\n\nPress Esc to skip",
+                platform::format_file_size(*scanned)
+            ),
+        ),
         DialogType::Progress { operation } => {
-            let progress = if operation.total_size > 0 {
-                (operation.processed_size as f64 / operation.total_size as f64 * 100.0) as u16
-            } else {
-                0
-            };
             let current_file = operation.current_file.as_deref().unwrap_or("Unknown");
-            let content = format!(
-                "Operation: {:?}\nCurrent file: {}\nProgress: {}%\nProcessed: {} / {}",
-                operation.operation_type,
-                current_file,
-                progress,
-                platform::format_file_size(operation.processed_size),
-                platform::format_file_size(operation.total_size)
-            );
+            let mut content = format!("Operation: {:?}\n", operation.operation_type);
+
+            // File counter: "File 37 of 214" when the pre-scan ran, otherwise
+            // just the running number
+            let file_number = operation.files_completed + 1;
+            if operation.files_total > 0 {
+                content.push_str(&format!(
+                    "File {} of {}: {}\n",
+                    file_number.min(operation.files_total),
+                    operation.files_total,
+                    current_file
+                ));
+            } else {
+                content.push_str(&format!("File {}: {}\n", file_number, current_file));
+            }
+
+            // Secondary bar for the file currently being copied
+            if operation.current_file_size > 0 {
+                content.push_str(&format!(
+                    "{} {} / {}\n",
+                    progress_bar(operation.current_file_processed, operation.current_file_size, 24),
+                    platform::format_file_size(operation.current_file_processed),
+                    platform::format_file_size(operation.current_file_size)
+                ));
+            }
+
+            // A zero total means the pre-scan was skipped; show indeterminate progress
+            if operation.total_size > 0 {
+                content.push_str(&format!(
+                    "{} Total: {} / {}\n",
+                    progress_bar(operation.processed_size, operation.total_size, 24),
+                    platform::format_file_size(operation.processed_size),
+                    platform::format_file_size(operation.total_size)
+                ));
+            } else {
+                content.push_str(&format!(
+                    "Total: {}\n",
+                    platform::format_file_size(operation.processed_size)
+                ));
+            }
+
+            content.push_str("\nPress Esc to cancel");
             ("Progress", content)
         },
+        DialogType::HotPath { ancestors, selected } => {
+            let mut content = String::new();
+            for (i, ancestor) in ancestors.iter().enumerate() {
+                let marker = if i == *selected { ">" } else { " " };
+                content.push_str(&format!("{} {}\n", marker, platform::path_to_display_string(ancestor)));
+            }
+            content.push_str("\nEnter Jump | Esc Close");
+            ("Go to ancestor", content)
+        },
+        DialogType::RecentFiles { files, selected } => {
+            let mut content = String::new();
+            for (i, file) in files.iter().enumerate() {
+                let marker = if i == *selected { ">" } else { " " };
+                content.push_str(&format!("{} {}\n", marker, platform::path_to_display_string(file)));
+            }
+            content.push_str("\nEnter Jump | Esc Close");
+            ("Recent files", content)
+        },
+        DialogType::CompareModeSelect { selected } => {
+            let mut content = String::from("Compare left and right panes using:\n\n");
+            for (i, mode) in COMPARE_MODES.iter().enumerate() {
+                let marker = if i == *selected { ">" } else { " " };
+                content.push_str(&format!("{} {}\n", marker, mode.label()));
+            }
+            content.push_str("\nEnter Compare | Esc Close");
+            ("Compare panes", content)
+        },
+        // Rendered separately above; unreachable here
+        DialogType::ContextMenu { .. } => return,
+    };
+
+    // Size the popup to its content so long paths aren't cut off by a
+    // fixed-width box; anything taller than the screen scrolls
+    let area = dialog_rect(&content, title.len(), f.size());
+    f.render_widget(Clear, area);
+
+    let inner_height = area.height.saturating_sub(2);
+    let line_count = content.lines().count() as u16;
+    let max_scroll = line_count.saturating_sub(inner_height);
+    let scroll = scroll.min(max_scroll);
+
+    let title = if max_scroll > 0 {
+        format!("{} (PgUp/PgDn to scroll)", title)
+    } else {
+        title.to_string()
     };
 
     let paragraph = Paragraph::new(content)
@@ -871,27 +3132,185 @@ fn render_dialog<B: tui::backend::Backend>(f: &mut Frame<B>, dialog: &DialogType
             .title(title)
             .border_style(Style::default().fg(config.colors.active_pane_border)))
         .alignment(Alignment::Left)
+        .scroll((scroll, 0))
         .wrap(tui::widgets::Wrap { trim: true });
-    
+
     f.render_widget(paragraph, area);
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-} 
\ No newline at end of file
+/// Center a dialog sized to its content: wide enough for the longest line
+/// and tall enough for every line, capped at 90% of the screen. Lines wider
+/// than the cap wrap and count for extra height.
+fn dialog_rect(content: &str, title_len: usize, screen: Rect) -> Rect {
+    let max_width = (screen.width as usize * 9 / 10).max(30);
+    let widest = content
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(title_len + 24); // room for the scroll hint in the title
+
+    // On very narrow terminals the 40-column floor can exceed max_width,
+    // and clamp panics on an inverted range
+    let width = (widest + 4).clamp(40.min(max_width), max_width) as u16;
+    let inner_width = (width as usize).saturating_sub(2).max(1);
+    let rows: usize = content
+        .lines()
+        .map(|line| line.chars().count().max(1).div_ceil(inner_width))
+        .sum();
+    let height = ((rows + 2) as u16).clamp(5, (screen.height * 9 / 10).max(5));
+
+    let x = screen.x + screen.width.saturating_sub(width) / 2;
+    let y = screen.y + screen.height.saturating_sub(height) / 2;
+    Rect { x, y, width, height }
+}
+
+/// Simple text progress bar, e.g. `[=========>          ] 45%`
+fn progress_bar(done: u64, total: u64, width: usize) -> String {
+    let percent = if total > 0 {
+        (done.min(total) as f64 / total as f64 * 100.0) as u16
+    } else {
+        0
+    };
+    let filled = width * percent as usize / 100;
+    let mut bar = String::with_capacity(width + 8);
+    bar.push('[');
+    for i in 0..width {
+        bar.push(if i < filled { '=' } else { ' ' });
+    }
+    bar.push(']');
+    bar.push_str(&format!(" {:>3}%", percent));
+    bar
+}
+
+fn render_context_menu<B: tui::backend::Backend>(
+    f: &mut Frame<B>,
+    selected: usize,
+    x: u16,
+    y: u16,
+    config: &Config,
+) {
+    let area = context_menu_rect(x, y, f.size());
+    f.render_widget(Clear, area);
+
+    let item_width = area.width.saturating_sub(3) as usize;
+    let items: Vec<ListItem> = CONTEXT_MENU_ITEMS.iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let style = if i == selected {
+                Style::default().bg(Color::Cyan).fg(Color::Black)
+            } else {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            };
+            ListItem::new(format!(" {:<width$}", item, width = item_width)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(config.colors.active_pane_border))
+            .style(Style::default().bg(Color::Blue)));
+    f.render_widget(list, area);
+}
+
+/// Box for the context menu, nudged left/up as needed to stay on screen
+fn context_menu_rect(x: u16, y: u16, screen: Rect) -> Rect {
+    let width = 14u16;
+    let height = CONTEXT_MENU_ITEMS.len() as u16 + 2;
+    Rect {
+        x: x.min(screen.width.saturating_sub(width)),
+        y: y.min(screen.height.saturating_sub(height)),
+        width: width.min(screen.width),
+        height: height.min(screen.height),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tui::backend::TestBackend;
+
+    /// Build an App on a headless TestBackend with the panes rooted in the
+    /// given directories, so whole key-driven flows can run in CI. Session
+    /// state (pending operation, recent files) goes to the returned TempDir
+    /// instead of reading — or worse, clobbering — the real state directory
+    /// of whoever runs the tests; keep it alive for the app's lifetime.
+    fn test_app(left: &std::path::Path, right: &std::path::Path) -> (App<TestBackend>, TempDir) {
+        let state = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.panels.left = left.to_path_buf();
+        config.panels.right = right.to_path_buf();
+        config.state_dir_override = Some(state.path().to_path_buf());
+        let terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut app = App::with_terminal(config, terminal).unwrap();
+        // The initial listings load in the background; the tests have no run
+        // loop polling for them, so wait here
+        while app.left_pane.loading || app.right_pane.loading {
+            app.poll_pane_loads();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        (app, state)
+    }
+
+    fn press(app: &mut App<TestBackend>, key: KeyCode) {
+        app.handle_key_event(key, KeyModifiers::NONE).unwrap();
+    }
+
+    #[test]
+    fn test_copy_flow_end_to_end() {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+        std::fs::write(left.path().join("a.txt"), "hello").unwrap();
+
+        let (mut app, _state) = test_app(left.path(), right.path());
+        app.draw().unwrap();
+
+        // Move off ".." onto a.txt, then F5 and Enter through the dialog
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::F(5));
+        assert!(matches!(app.current_dialog, Some(DialogType::Input { .. })));
+        press(&mut app, KeyCode::Enter);
+
+        // The operation runs on a worker thread; poll until it lands
+        for _ in 0..200 {
+            app.poll_operation().unwrap();
+            if app.active_operation.is_none() && app.operation_handle.is_none() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert_eq!(
+            std::fs::read_to_string(right.path().join("a.txt")).unwrap(),
+            "hello"
+        );
+        // poll_operation refreshed the destination pane
+        assert!(app.right_pane.entries.iter().any(|e| e.name == "a.txt"));
+        app.draw().unwrap();
+    }
+
+    #[test]
+    fn test_quit_key_sets_should_quit() {
+        let left = TempDir::new().unwrap();
+        let right = TempDir::new().unwrap();
+
+        let (mut app, _state) = test_app(left.path(), right.path());
+        press(&mut app, KeyCode::F(10));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_dialog_rect_fits_narrow_terminals() {
+        // Narrower than the 40-column floor used to invert the clamp range
+        // and panic on every dialog render
+        for width in [20u16, 44, 80] {
+            let screen = Rect { x: 0, y: 0, width, height: 10 };
+            let rect = dialog_rect("line one\nline two", 5, screen);
+            assert!(rect.width <= screen.width.max(30));
+            assert!(rect.height <= screen.height.max(5));
+        }
+    }
+}
@@ -2,19 +2,35 @@ use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
 };
 use crate::error::{GeekCommanderError, Result};
 use crate::platform;
 
-const MAX_FILE_SIZE_FOR_VIEWING: u64 = 50 * 1024 * 1024; // 50MB
+/// Built-in viewer cap, used when `[ViewerLimits]` has no entry for the
+/// file's extension and no `default` override
+pub const MAX_FILE_SIZE_FOR_VIEWING: u64 = 50 * 1024 * 1024; // 50MB
+/// How much of a file the hex override reads; the dump notes the cut
+const MAX_HEX_DUMP_SIZE: u64 = 16 * 1024 * 1024; // 16MB
 const BUFFER_SIZE: usize = 64 * 1024; // 64KB
+/// Columns moved per horizontal scroll step
+const HORIZONTAL_SCROLL_STEP: usize = 16;
+
+/// What a save prompt writes when confirmed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveTarget {
+    /// Only the marked line range
+    Marked,
+    /// The full content as displayed (after decoding)
+    All,
+}
 
 #[derive(Debug, Clone)]
 pub struct FileViewer {
@@ -22,20 +38,35 @@ pub struct FileViewer {
     pub lines: Vec<String>,
     pub current_line: usize,
     pub scroll_offset: usize,
+    /// First visible column; lines are windowed rather than wrapped so a
+    /// single multi-megabyte line cannot blow up render time
+    pub horizontal_offset: usize,
     pub file_path: String,
     pub file_size: u64,
     pub is_binary: bool,
+    /// Start of the marked range; the cursor line is the other end
+    pub mark_anchor: Option<usize>,
+    /// Target and filename being typed, when the save prompt is open
+    pub save_prompt: Option<(SaveTarget, String)>,
+    /// Transient feedback shown in the status line after an action
+    pub status_message: Option<String>,
 }
 
 impl FileViewer {
     pub fn new(file_path: &Path) -> Result<Self> {
+        Self::new_with_limit(file_path, MAX_FILE_SIZE_FOR_VIEWING)
+    }
+
+    /// Like [`FileViewer::new`] but with an explicit size cap, so callers
+    /// can apply the per-extension `[ViewerLimits]` configuration
+    pub fn new_with_limit(file_path: &Path, max_size: u64) -> Result<Self> {
         let metadata = fs::metadata(file_path)?;
         let file_size = metadata.len();
-        
-        if file_size > MAX_FILE_SIZE_FOR_VIEWING {
+
+        if file_size > max_size {
             return Err(GeekCommanderError::FileOperation(format!(
                 "File is too large to view ({} bytes). Maximum size is {} bytes.",
-                file_size, MAX_FILE_SIZE_FOR_VIEWING
+                file_size, max_size
             )));
         }
 
@@ -55,9 +86,13 @@ impl FileViewer {
                 ],
                 current_line: 0,
                 scroll_offset: 0,
+                horizontal_offset: 0,
                 file_path: file_path.to_string_lossy().to_string(),
                 file_size,
                 is_binary: true,
+                mark_anchor: None,
+                save_prompt: None,
+                status_message: None,
             });
         }
 
@@ -70,12 +105,83 @@ impl FileViewer {
             lines,
             current_line: 0,
             scroll_offset: 0,
+            horizontal_offset: 0,
             file_path: file_path.to_string_lossy().to_string(),
             file_size,
             is_binary: false,
+            mark_anchor: None,
+            save_prompt: None,
+            status_message: None,
         })
     }
 
+    /// View a file as a hex dump, regardless of size or binariness — the
+    /// "view anyway" override when a file was rejected. Only the first
+    /// [`MAX_HEX_DUMP_SIZE`] bytes are read; the dump notes any cut.
+    pub fn new_hex(file_path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(file_path)?;
+        let file_size = metadata.len();
+
+        let file = fs::File::open(file_path)?;
+        let mut buffer = Vec::new();
+        file.take(MAX_HEX_DUMP_SIZE).read_to_end(&mut buffer)?;
+
+        let mut lines: Vec<String> = buffer
+            .chunks(16)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+                let ascii: String = chunk
+                    .iter()
+                    .map(|&b| if (0x20..0x7f).contains(&b) { b as char } else { '.' })
+                    .collect();
+                format!("{:08x}  {:<47}  |{}|", i * 16, hex.join(" "), ascii)
+            })
+            .collect();
+        if file_size > MAX_HEX_DUMP_SIZE {
+            lines.push(format!(
+                "... truncated, showing the first {} of {} bytes",
+                platform::format_file_size(MAX_HEX_DUMP_SIZE),
+                platform::format_file_size(file_size)
+            ));
+        }
+
+        Ok(FileViewer {
+            content: lines.join("\n"),
+            lines,
+            current_line: 0,
+            scroll_offset: 0,
+            horizontal_offset: 0,
+            file_path: format!("{} (hex)", file_path.to_string_lossy()),
+            file_size,
+            is_binary: false,
+            mark_anchor: None,
+            save_prompt: None,
+            status_message: None,
+        })
+    }
+
+    /// View generated text (such as a directory summary) instead of a file
+    /// on disk; `title` takes the place of the file path in the header
+    pub fn from_content(title: String, content: String) -> Self {
+        let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+        let file_size = content.len() as u64;
+
+        FileViewer {
+            content,
+            lines,
+            current_line: 0,
+            scroll_offset: 0,
+            horizontal_offset: 0,
+            file_path: title,
+            file_size,
+            is_binary: false,
+            mark_anchor: None,
+            save_prompt: None,
+            status_message: None,
+        }
+    }
+
     pub fn scroll_up(&mut self) {
         if self.current_line > 0 {
             self.current_line -= 1;
@@ -113,6 +219,23 @@ impl FileViewer {
     pub fn home(&mut self) {
         self.current_line = 0;
         self.scroll_offset = 0;
+        self.horizontal_offset = 0;
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.horizontal_offset = self.horizontal_offset.saturating_sub(HORIZONTAL_SCROLL_STEP);
+    }
+
+    pub fn scroll_right(&mut self) {
+        // Bounded by the longest visible line so the view cannot wander off
+        // into empty space forever
+        let max_offset = self.lines[self.scroll_offset.min(self.lines.len())..]
+            .iter()
+            .take(256)
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        self.horizontal_offset = (self.horizontal_offset + HORIZONTAL_SCROLL_STEP).min(max_offset);
     }
 
     pub fn end(&mut self, visible_lines: usize) {
@@ -120,6 +243,69 @@ impl FileViewer {
         self.scroll_offset = self.lines.len().saturating_sub(visible_lines);
     }
 
+    /// Start marking at the cursor line, or clear an existing mark
+    pub fn toggle_mark(&mut self) {
+        self.mark_anchor = match self.mark_anchor {
+            Some(_) => None,
+            None => Some(self.current_line),
+        };
+    }
+
+    /// The marked line range as inclusive (first, last) indices, regardless of
+    /// which direction the mark was extended in
+    pub fn marked_range(&self) -> Option<(usize, usize)> {
+        self.mark_anchor.map(|anchor| {
+            (anchor.min(self.current_line), anchor.max(self.current_line))
+        })
+    }
+
+    /// The text of the marked lines, joined with newlines
+    pub fn marked_text(&self) -> Option<String> {
+        self.marked_range().map(|(first, last)| {
+            let last = last.min(self.lines.len().saturating_sub(1));
+            self.lines[first..=last].join("\n")
+        })
+    }
+
+    /// Write the displayed content (as decoded, not the raw bytes) to `path`,
+    /// returning how many lines were written
+    pub fn save_displayed(&self, path: &Path) -> Result<usize> {
+        let mut content = self.lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(path, content)?;
+        Ok(self.lines.len())
+    }
+
+    /// Write the marked lines to `path`, returning how many were written
+    pub fn save_marked(&self, path: &Path) -> Result<usize> {
+        let (first, last) = self.marked_range().ok_or_else(|| {
+            GeekCommanderError::FileOperation("No lines are marked".to_string())
+        })?;
+        let last = last.min(self.lines.len().saturating_sub(1));
+
+        let mut content = self.lines[first..=last].join("\n");
+        content.push('\n');
+        fs::write(path, content)?;
+        Ok(last - first + 1)
+    }
+
+    fn copy_marked_to_clipboard(&mut self) {
+        let text = match self.marked_text() {
+            Some(text) => text,
+            None => {
+                self.status_message = Some("Nothing marked - press v to start marking".to_string());
+                return;
+            },
+        };
+        let line_count = text.lines().count();
+        self.status_message = Some(match platform::copy_text_to_clipboard(&text) {
+            Ok(()) => format!("Copied {} line(s) to clipboard", line_count),
+            Err(e) => format!("Copy failed: {}", e),
+        });
+    }
+
     pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -141,27 +327,67 @@ impl FileViewer {
         let visible_lines = chunks[1].height as usize;
         let end_line = (self.scroll_offset + visible_lines).min(self.lines.len());
         
-        let visible_content = if self.lines.is_empty() {
-            String::new()
-        } else {
-            self.lines[self.scroll_offset..end_line].join("\n")
-        };
+        let marked = self.marked_range();
+        let window_width = (chunks[1].width as usize).saturating_sub(2).max(1);
+        let mut any_truncated = false;
+        let visible_content: Vec<Spans> = self.lines[self.scroll_offset.min(self.lines.len())..end_line]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line_index = self.scroll_offset + i;
+                let style = match marked {
+                    Some((first, last)) if line_index >= first && line_index <= last => {
+                        Style::default().fg(Color::Black).bg(Color::Cyan)
+                    },
+                    _ => Style::default().fg(Color::White),
+                };
+                let (window, truncated) = line_window(line, self.horizontal_offset, window_width);
+                if truncated {
+                    any_truncated = true;
+                    Spans::from(vec![
+                        Span::styled(window.to_string(), style),
+                        Span::styled("»", Style::default().fg(Color::Yellow)),
+                    ])
+                } else {
+                    Spans::from(Span::styled(window.to_string(), style))
+                }
+            })
+            .collect();
 
         let content_paragraph = Paragraph::new(visible_content)
             .block(Block::default().borders(Borders::ALL).title("Content"))
-            .wrap(Wrap { trim: false })
             .style(Style::default().fg(Color::White));
         f.render_widget(content_paragraph, chunks[1]);
 
         // Status
-        let status = if self.is_binary {
+        let status = if let Some((target, ref name)) = self.save_prompt {
+            let what = match target {
+                SaveTarget::Marked => "marked lines",
+                SaveTarget::All => "displayed content",
+            };
+            format!("Save {} to: {}_  (Enter to save, Esc to cancel)", what, name)
+        } else if let Some(ref message) = self.status_message {
+            message.clone()
+        } else if self.is_binary {
             "Binary file - F10/Esc to exit".to_string()
-        } else {
+        } else if let Some((first, last)) = marked {
             format!(
-                "Line {}/{} | ↑↓ Scroll | PgUp/PgDn Page | Home/End | F10/Esc Exit",
+                "Marked {}-{} | c Copy | s Save to file | v/Esc Unmark",
+                first + 1,
+                last + 1
+            )
+        } else {
+            let mut status = format!(
+                "Line {}/{} | ↑↓ Scroll | PgUp/PgDn Page | Home/End | v Mark | S Save as | F10/Esc Exit",
                 self.current_line + 1,
                 self.lines.len()
-            )
+            );
+            if self.horizontal_offset > 0 {
+                status = format!("Col {} | {}", self.horizontal_offset + 1, status);
+            } else if any_truncated {
+                status = format!("Line truncated - ←/→ to scroll | {}", status);
+            }
+            status
         };
         
         let status_paragraph = Paragraph::new(status)
@@ -170,19 +396,91 @@ impl FileViewer {
         f.render_widget(status_paragraph, chunks[2]);
     }
 
-    pub fn handle_key(&mut self, key: KeyCode, visible_lines: usize) -> bool {
+    pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers, visible_lines: usize) -> bool {
+        // The save prompt captures all input while it is open
+        if self.save_prompt.is_some() {
+            self.handle_save_prompt_key(key);
+            return true;
+        }
+
+        self.status_message = None;
+
         match key {
+            KeyCode::Esc if self.mark_anchor.is_some() => self.mark_anchor = None,
             KeyCode::F(10) | KeyCode::Esc => return false, // Exit viewer
-            KeyCode::Up => self.scroll_up(),
-            KeyCode::Down => self.scroll_down(visible_lines),
+            KeyCode::Up => {
+                // Shift+Up starts or extends a mark while moving
+                if modifiers.contains(KeyModifiers::SHIFT) && self.mark_anchor.is_none() {
+                    self.mark_anchor = Some(self.current_line);
+                }
+                self.scroll_up();
+            },
+            KeyCode::Down => {
+                if modifiers.contains(KeyModifiers::SHIFT) && self.mark_anchor.is_none() {
+                    self.mark_anchor = Some(self.current_line);
+                }
+                self.scroll_down(visible_lines);
+            },
+            KeyCode::Left => self.scroll_left(),
+            KeyCode::Right => self.scroll_right(),
             KeyCode::PageUp => self.page_up(visible_lines),
             KeyCode::PageDown => self.page_down(visible_lines),
             KeyCode::Home => self.home(),
             KeyCode::End => self.end(visible_lines),
+            KeyCode::Char('v') => self.toggle_mark(),
+            KeyCode::Char('c') => self.copy_marked_to_clipboard(),
+            KeyCode::Char('s') => {
+                if self.mark_anchor.is_some() {
+                    self.save_prompt = Some((SaveTarget::Marked, String::new()));
+                } else {
+                    self.status_message = Some("Nothing marked - press v to start marking".to_string());
+                }
+            },
+            KeyCode::Char('S') => {
+                if self.is_binary {
+                    self.status_message = Some("Binary content cannot be saved from the viewer".to_string());
+                } else {
+                    self.save_prompt = Some((SaveTarget::All, String::new()));
+                }
+            },
             _ => {} // Ignore other keys
         }
         true // Continue viewing
     }
+
+    fn handle_save_prompt_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                let (target, name) = match self.save_prompt.take() {
+                    Some(prompt) => prompt,
+                    None => return,
+                };
+                if name.is_empty() {
+                    return;
+                }
+                let result = match target {
+                    SaveTarget::Marked => self.save_marked(Path::new(&name)),
+                    SaveTarget::All => self.save_displayed(Path::new(&name)),
+                };
+                self.status_message = Some(match result {
+                    Ok(count) => format!("Saved {} line(s) to {}", count, name),
+                    Err(e) => format!("Save failed: {}", e),
+                });
+            },
+            KeyCode::Esc => self.save_prompt = None,
+            KeyCode::Backspace => {
+                if let Some((_, ref mut name)) = self.save_prompt {
+                    name.pop();
+                }
+            },
+            KeyCode::Char(c) => {
+                if let Some((_, ref mut name)) = self.save_prompt {
+                    name.push(c);
+                }
+            },
+            _ => {},
+        }
+    }
 }
 
 pub fn launch_external_editor(file_path: &Path) -> Result<()> {
@@ -214,6 +512,21 @@ pub fn launch_external_editor(file_path: &Path) -> Result<()> {
     }
 }
 
+/// Take a `width`-character window of `line` starting at column `start`,
+/// without scanning or copying the rest of the line. Returns the slice and
+/// whether the line continues past the window.
+fn line_window(line: &str, start: usize, width: usize) -> (&str, bool) {
+    let mut indices = line.char_indices().skip(start);
+    let begin = match indices.next() {
+        Some((byte_index, _)) => byte_index,
+        None => return ("", false),
+    };
+    match indices.nth(width.saturating_sub(1)) {
+        Some((byte_index, _)) => (&line[begin..byte_index], true),
+        None => (&line[begin..], false),
+    }
+}
+
 fn is_binary_content(buffer: &[u8]) -> bool {
     // Simple binary detection: check for null bytes and high ratio of non-printable characters
     let null_count = buffer.iter().filter(|&&b| b == 0).count();
@@ -269,6 +582,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_new_with_limit_rejects_over_cap() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.log");
+        fs::write(&test_file, "0123456789").unwrap();
+
+        assert!(FileViewer::new_with_limit(&test_file, 5).is_err());
+        assert!(FileViewer::new_with_limit(&test_file, 10).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_dump_view() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let binary_file = temp_dir.path().join("data.bin");
+        let mut bytes = vec![0u8, 1, 2, 255];
+        bytes.extend_from_slice(b"ABC");
+        fs::write(&binary_file, &bytes).unwrap();
+
+        let viewer = FileViewer::new_hex(&binary_file)?;
+
+        assert_eq!(viewer.lines.len(), 1);
+        assert!(viewer.lines[0].starts_with("00000000"));
+        assert!(viewer.lines[0].contains("00 01 02 ff 41 42 43"));
+        // Non-printable bytes show as dots in the ASCII gutter
+        assert!(viewer.lines[0].contains("|....ABC|"));
+        assert!(!viewer.is_binary);
+
+        Ok(())
+    }
+
     #[test]
     fn test_viewer_navigation() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
@@ -332,6 +677,108 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mark_range_and_save() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.txt");
+
+        let content = (0..10).map(|i| format!("Line {}", i + 1)).collect::<Vec<_>>().join("\n");
+        fs::write(&test_file, content).unwrap();
+
+        let mut viewer = FileViewer::new(&test_file)?;
+        assert_eq!(viewer.marked_range(), None);
+
+        // Mark lines 3..=5 by anchoring at line 3 and moving down twice
+        viewer.current_line = 2;
+        viewer.toggle_mark();
+        viewer.scroll_down(10);
+        viewer.scroll_down(10);
+        assert_eq!(viewer.marked_range(), Some((2, 4)));
+        assert_eq!(viewer.marked_text().as_deref(), Some("Line 3\nLine 4\nLine 5"));
+
+        // Marking upward from the anchor gives the same ordered range
+        viewer.current_line = 0;
+        assert_eq!(viewer.marked_range(), Some((0, 2)));
+
+        // Save the marked lines to a new file
+        viewer.current_line = 4;
+        let output = temp_dir.path().join("snippet.txt");
+        let count = viewer.save_marked(&output)?;
+        assert_eq!(count, 3);
+        assert_eq!(fs::read_to_string(&output).unwrap(), "Line 3\nLine 4\nLine 5\n");
+
+        // Toggling again clears the mark
+        viewer.toggle_mark();
+        assert_eq!(viewer.marked_range(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_displayed() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.txt");
+
+        // Invalid UTF-8 gets decoded lossily; the save writes what is displayed
+        let mut bytes = b"Line 1\nLine ".to_vec();
+        bytes.push(0xFF);
+        bytes.extend_from_slice(b"2\nLine 3");
+        fs::write(&test_file, bytes).unwrap();
+
+        let viewer = FileViewer::new(&test_file)?;
+        let output = temp_dir.path().join("saved.txt");
+        let count = viewer.save_displayed(&output)?;
+
+        assert_eq!(count, 3);
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            format!("Line 1\nLine {}2\nLine 3\n", char::REPLACEMENT_CHARACTER)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_window() {
+        assert_eq!(line_window("hello", 0, 10), ("hello", false));
+        assert_eq!(line_window("hello", 0, 5), ("hello", false));
+        assert_eq!(line_window("hello world", 0, 5), ("hello", true));
+        assert_eq!(line_window("hello world", 6, 5), ("world", false));
+        assert_eq!(line_window("hello", 10, 5), ("", false));
+        // Multi-byte characters are windowed by character, not by byte
+        assert_eq!(line_window("héllo wörld", 2, 4), ("llo ", true));
+    }
+
+    #[test]
+    fn test_horizontal_scroll() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("minified.js");
+
+        fs::write(&test_file, "x".repeat(1000)).unwrap();
+
+        let mut viewer = FileViewer::new(&test_file)?;
+        assert_eq!(viewer.horizontal_offset, 0);
+
+        viewer.scroll_right();
+        assert_eq!(viewer.horizontal_offset, HORIZONTAL_SCROLL_STEP);
+        viewer.scroll_left();
+        assert_eq!(viewer.horizontal_offset, 0);
+
+        // Cannot scroll left past the start or right past the longest line
+        viewer.scroll_left();
+        assert_eq!(viewer.horizontal_offset, 0);
+        for _ in 0..200 {
+            viewer.scroll_right();
+        }
+        assert_eq!(viewer.horizontal_offset, 1000);
+
+        // Home resets the horizontal position too
+        viewer.home();
+        assert_eq!(viewer.horizontal_offset, 0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_empty_file() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();